@@ -1,40 +1,64 @@
-use crate::{shared::PuzzleResult, test_solvers};
+use aoc_framework::{
+    generate::Rng,
+    diagnostic::Diagnostic,
+    shared::{PuzzleResult, Solution, SolveError},
+    test_solvers,
+};
 
-pub fn part1(input: &str) -> PuzzleResult {
-    let elf_calories = transform_input(input);
+pub struct Day1;
 
-    elf_calories
-        .iter()
-        .map(|calories| calories.iter().sum::<i64>())
-        .max()
-        .unwrap_or(0)
-        .into()
-}
+impl Solution for Day1 {
+    type Parsed = Vec<Vec<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        transform_input(input)
+    }
+
+    fn part1(elf_calories: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        Ok(elf_calories
+            .iter()
+            .map(|calories| calories.iter().sum::<i64>())
+            .max()
+            .unwrap_or(0)
+            .into())
+    }
+
+    fn part2(elf_calories: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        let top_3_calories = elf_calories
+            .iter()
+            .map(|calories| calories.iter().sum::<i64>())
+            .fold([0, 0, 0], |mut top_3_calories, total_calories| {
+                let (lowest_index, &lowest_calories) = top_3_calories
+                    .iter()
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| a.cmp(b))
+                    .unwrap();
 
-pub fn part2(input: &str) -> PuzzleResult {
-    let elf_calories = transform_input(input);
-
-    let top_3_calories = elf_calories
-        .iter()
-        .map(|calories| calories.iter().sum::<i64>())
-        .fold([0, 0, 0], |mut top_3_calories, total_calories| {
-            let (lowest_index, &lowest_calories) = top_3_calories
-                .iter()
-                .enumerate()
-                .min_by(|(_, a), (_, b)| a.cmp(b))
-                .unwrap();
-
-            if total_calories > lowest_calories {
-                top_3_calories[lowest_index] = total_calories
-            }
-            top_3_calories
-        });
-
-    top_3_calories.iter().sum::<i64>().into()
+                if total_calories > lowest_calories {
+                    top_3_calories[lowest_index] = total_calories
+                }
+                top_3_calories
+            });
+
+        Ok(top_3_calories.iter().sum::<i64>().into())
+    }
+
+    /// `size` elves carrying 1 to 15 items each.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let elves = (0..size.max(1))
+            .map(|_| {
+                (0..rng.range(1..=15))
+                    .map(|_| format!("{}\n", rng.range(1000..=60000)))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+
+        Some(elves.join("\n"))
+    }
 }
 
-fn transform_input(input: &str) -> Vec<Vec<i64>> {
-    input
+fn transform_input(input: &str) -> Result<Vec<Vec<i64>>, SolveError> {
+    let elf_calories = input
         .split_terminator("\n\n")
         .map(|items_calories| {
             items_calories
@@ -42,11 +66,13 @@ fn transform_input(input: &str) -> Vec<Vec<i64>> {
                 .map(|item_calories| {
                     item_calories
                         .parse::<i64>()
-                        .expect("item calorie should be a number")
+                        .map_err(|err| Diagnostic::new(err, input, item_calories))
                 })
-                .collect::<Vec<i64>>()
+                .collect::<Result<Vec<i64>, _>>()
         })
-        .collect::<Vec<Vec<i64>>>()
+        .collect::<Result<Vec<Vec<i64>>, _>>()?;
+
+    Ok(elf_calories)
 }
 
-test_solvers!(24_000, 45_000);
+test_solvers!(Day1);
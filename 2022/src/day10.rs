@@ -1,72 +1,106 @@
-mod cpu;
-mod crt;
+pub mod cpu;
+pub mod crt;
 
 use std::str::FromStr;
 
-use crate::{shared::PuzzleResult, test_solvers};
+use aoc_framework::{
+    generate::Rng,
+    ocr,
+    shared::{PuzzleResult, Solution, SolveError},
+    test_solvers,
+};
 
 use cpu::{Instruction, CPU};
 use crt::CRT;
 
-pub fn part1(input: &str) -> PuzzleResult {
-    const SIGNAL_STRENGTH_MEASURE_CYCLES: [u32; 6] = [20, 60, 100, 140, 180, 220];
+pub struct Day10;
 
-    let instructions = transform_input(input);
+impl Solution for Day10 {
+    type Parsed = Vec<Instruction>;
 
-    let mut cpu = CPU::new();
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        transform_input(input)
+    }
+
+    fn part1(instructions: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        const SIGNAL_STRENGTH_MEASURE_CYCLES: [u32; 6] = [20, 60, 100, 140, 180, 220];
 
-    let mut signal_strengths = vec![];
+        let mut cpu = CPU::new();
 
-    for instruction in instructions {
-        cpu.exec(instruction);
-        while cpu.is_busy() {
-            let (cycle, register_x) = cpu.run_cycle();
-            if SIGNAL_STRENGTH_MEASURE_CYCLES.contains(&cycle) {
-                signal_strengths.push((cycle as i32) * register_x);
+        let mut signal_strengths = vec![];
+
+        for instruction in instructions {
+            cpu.exec(*instruction);
+            while cpu.is_busy() {
+                let (cycle, register_x) = cpu.run_cycle();
+                if SIGNAL_STRENGTH_MEASURE_CYCLES.contains(&cycle) {
+                    signal_strengths.push((cycle as i32) * register_x);
+                }
             }
         }
-    }
 
-    let result = signal_strengths.into_iter().sum::<i32>() as i64;
+        let result = signal_strengths.into_iter().sum::<i32>() as i64;
 
-    result.into()
-}
+        Ok(result.into())
+    }
 
-pub fn part2(input: &str) -> PuzzleResult {
-    let instructions = transform_input(input);
+    fn part2(instructions: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        let mut cpu = CPU::new();
 
-    let mut cpu = CPU::new();
+        let mut crt = CRT::new();
 
-    let mut crt = CRT::new();
+        for instruction in instructions {
+            cpu.exec(*instruction);
+            while cpu.is_busy() {
+                let (cycle, register_x) = cpu.run_cycle();
 
-    for instruction in instructions {
-        cpu.exec(instruction);
-        while cpu.is_busy() {
-            let (cycle, register_x) = cpu.run_cycle();
+                if !CRT::is_on_screen(cycle) {
+                    return Err(SolveError::invalid_input(format!(
+                        "program should finish within the {} cycles it takes to draw the screen",
+                        CRT::PIXELS
+                    )));
+                }
 
-            crt.draw(cycle, register_x);
+                crt.draw(cycle, register_x);
+            }
         }
+
+        Ok(ocr::to_answer(crt.into_grid()))
     }
 
-    crt.to_string().into()
+    /// A program running for `size` cycles, at most the 240 it takes to draw the screen. The
+    /// sprite stays on the screen.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let cycles = size.clamp(1, CRT::PIXELS);
+        let mut program = String::new();
+        let mut register_x = 1;
+        let mut cycle = 0;
+
+        while cycle < cycles {
+            if cycle + 2 <= cycles && rng.ratio(2, 3) {
+                let next_x = rng.range(0..=39) as i32;
+                program.push_str(&format!("addx {}\n", next_x - register_x));
+                register_x = next_x;
+                cycle += 2;
+            } else {
+                program.push_str("noop\n");
+                cycle += 1;
+            }
+        }
+
+        Some(program)
+    }
 }
 
-fn transform_input(input: &str) -> Vec<Instruction> {
-    input
+fn transform_input(input: &str) -> Result<Vec<Instruction>, SolveError> {
+    let instructions = input
         .lines()
-        .filter_map(|input| Instruction::from_str(input).ok())
-        .collect()
+        .map(|instruction| {
+            Instruction::from_str(instruction).map_err(|err| err.relative_to(input, instruction))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(instructions)
 }
 
-test_solvers!(
-    13140,
-    "
-##..##..##..##..##..##..##..##..##..##..
-###...###...###...###...###...###...###.
-####....####....####....####....####....
-#####.....#####.....#####.....#####.....
-######......######......######......####
-#######.......#######.......#######.....
-"
-    .to_owned()
-);
+test_solvers!(Day10);
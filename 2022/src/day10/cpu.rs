@@ -1,6 +1,8 @@
-use std::{num::ParseIntError, str::FromStr};
+use std::{error::Error, fmt::Display, num::ParseIntError, str::FromStr};
 
-#[derive(Debug)]
+use aoc_framework::diagnostic::Diagnostic;
+
+#[derive(Debug, Clone, Copy)]
 pub enum Instruction {
     Noop,
     AddX(i32),
@@ -28,22 +30,37 @@ pub enum ParseInstructionError {
     InvalidAddXValueError(ParseIntError),
 }
 
+impl Display for ParseInstructionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidInstructionError => write!(f, "expected 'noop' or 'addx <value>'"),
+            Self::InvalidAddXValueError(err) => write!(f, "invalid addx value: {err}"),
+        }
+    }
+}
+
+impl Error for ParseInstructionError {}
+
 impl FromStr for Instruction {
-    type Err = ParseInstructionError;
+    type Err = Diagnostic<ParseInstructionError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let parts = s.split(' ').collect::<Vec<_>>();
 
         match parts.as_slice() {
             ["noop"] => Ok(Self::Noop),
-            ["addx", value] => {
-                let value = value
-                    .parse()
-                    .map_err(ParseInstructionError::InvalidAddXValueError)?;
+            &["addx", value] => {
+                let value = value.parse().map_err(|err| {
+                    Diagnostic::new(ParseInstructionError::InvalidAddXValueError(err), s, value)
+                })?;
 
                 Ok(Self::AddX(value))
             }
-            _ => Err(ParseInstructionError::InvalidInstructionError),
+            _ => Err(Diagnostic::new(
+                ParseInstructionError::InvalidInstructionError,
+                s,
+                s,
+            )),
         }
     }
 }
@@ -61,7 +78,7 @@ impl CPUInstruction {
         }
     }
 
-    pub fn run_cycle(&mut self) -> () {
+    pub fn run_cycle(&mut self) {
         self.running_for += 1
     }
 
@@ -80,6 +97,12 @@ pub struct CPU {
     current_instruction: Option<CPUInstruction>,
 }
 
+impl Default for CPU {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CPU {
     pub fn new() -> Self {
         Self {
@@ -114,7 +137,7 @@ impl CPU {
         (self.cycle, saved_register_x)
     }
 
-    pub fn exec(&mut self, instruction: Instruction) -> () {
+    pub fn exec(&mut self, instruction: Instruction) {
         self.current_instruction = Some(CPUInstruction::new(instruction));
     }
 }
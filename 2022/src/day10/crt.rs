@@ -1,21 +1,31 @@
-use std::{fmt::Display, ops::Range};
+use std::ops::Range;
+
+use aoc_framework::shared::Grid;
 
 const CRT_WIDTH: usize = 40;
 const CRT_HEIGHT: usize = 6;
 const SPRITE_SIZE: i32 = 3;
 
 pub struct CRT {
-    pixels: [[char; CRT_WIDTH]; CRT_HEIGHT],
+    pixels: Grid,
+}
+
+impl Default for CRT {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl CRT {
+    pub const PIXELS: usize = CRT_WIDTH * CRT_HEIGHT;
+
     pub fn new() -> Self {
         Self {
-            pixels: [['.'; CRT_WIDTH]; CRT_HEIGHT],
+            pixels: Grid::new(CRT_WIDTH, CRT_HEIGHT),
         }
     }
 
-    pub fn draw(&mut self, cpu_cycle: u32, register_x: i32) -> () {
+    pub fn draw(&mut self, cpu_cycle: u32, register_x: i32) {
         let row = Self::row(cpu_cycle);
         let column = Self::column(cpu_cycle);
 
@@ -26,8 +36,18 @@ impl CRT {
         self.set_pixel(row, column, pixel)
     }
 
-    fn set_pixel(&mut self, row: usize, column: usize, pixel: char) -> () {
-        self.pixels[row][column] = pixel;
+    /// The picture drawn on the screen.
+    pub fn into_grid(self) -> Grid {
+        self.pixels
+    }
+
+    /// Whether the pixel drawn during the cycle fits on the screen.
+    pub fn is_on_screen(cpu_cycle: u32) -> bool {
+        (1..=Self::PIXELS).contains(&(cpu_cycle as usize))
+    }
+
+    fn set_pixel(&mut self, row: usize, column: usize, is_lit: bool) {
+        self.pixels.set(column, row, is_lit);
     }
 
     fn pixel_index(cycle: u32) -> usize {
@@ -42,14 +62,8 @@ impl CRT {
         Self::pixel_index(cycle) % CRT_WIDTH
     }
 
-    fn pixel(sprite: Range<i32>, column: usize) -> char {
-        let is_lit_pixel = sprite.contains(&(column as i32));
-
-        if is_lit_pixel {
-            '#'
-        } else {
-            '.'
-        }
+    fn pixel(sprite: Range<i32>, column: usize) -> bool {
+        sprite.contains(&(column as i32))
     }
 
     fn sprite_position(register_x: i32) -> Range<i32> {
@@ -58,18 +72,3 @@ impl CRT {
         sprite_start_index..(sprite_start_index + SPRITE_SIZE)
     }
 }
-
-impl Display for CRT {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "\n")?;
-
-        for row in self.pixels {
-            for pixel in row {
-                write!(f, "{}", pixel)?;
-            }
-            writeln!(f)?;
-        }
-
-        Ok(())
-    }
-}
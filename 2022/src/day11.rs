@@ -1,62 +1,142 @@
-use std::str::FromStr;
+use std::{fmt::Display, mem, str::FromStr};
 
-use crate::{shared::PuzzleResult, test_solvers};
+use aoc_framework::{
+    generate::Rng,
+    shared::{PuzzleResult, ReferenceSolver, Solution, SolveError},
+    test_solvers,
+};
 
 use monkey::Monkey;
 
-mod monkey;
+pub mod monkey;
+pub mod reference;
 
-pub fn part1(input: &str) -> PuzzleResult {
-    let mut monkeys = transform_input(input);
+pub struct Day11;
 
-    for _round in 0..20 {
-        for monkey_id in 0..monkeys.len() {
-            let monkey = &mut monkeys[monkey_id];
-            let mut throws = vec![];
-            while monkey.has_items() {
-                let item = monkey.inspect_item();
-                let item = monkey.lower_worry_level(item);
-                let catcher = monkey.get_catcher(item);
-                let throw = (catcher, item);
-                throws.push(throw);
-            }
+impl Solution for Day11 {
+    type Parsed = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        transform_input(input)
+    }
 
-            for (to, item) in throws {
-                let catcher = &mut monkeys[to];
-                catcher.catch_item(item);
+    fn part1(monkeys: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        let mut monkeys = monkeys.clone();
+
+        for _round in 0..20 {
+            for monkey_id in 0..monkeys.len() {
+                let monkey = &mut monkeys[monkey_id];
+                let mut throws = vec![];
+                while monkey.has_items() {
+                    let item = monkey.inspect_item();
+                    let item = Monkey::lower_worry_level(item);
+                    let catcher = monkey.get_catcher(item);
+                    let throw = (catcher, item);
+                    throws.push(throw);
+                }
+
+                for (to, item) in throws {
+                    let catcher = &mut monkeys[to];
+                    catcher.catch_item(item);
+                }
             }
         }
+
+        Ok(monkey_business_level(&monkeys))
     }
 
-    monkey_business_level(&monkeys)
-}
+    fn part2(monkeys: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        let mut monkeys = monkeys.clone();
+        // the least common multiple rather than the product, which overflows with many monkeys
+        let modulo = monkeys.iter().fold(1, |acc, monkey| lcm(acc, monkey.test()));
 
-pub fn part2(input: &str) -> PuzzleResult {
-    let mut monkeys = transform_input(input);
-    let modulo = monkeys.iter().fold(1, |acc, monkey| acc * monkey.test());
-
-    for _round in 0..10000 {
-        for monkey_id in 0..monkeys.len() {
-            let monkey = &mut monkeys[monkey_id];
-            let mut throws = vec![];
-            while monkey.has_items() {
-                let item = monkey.inspect_item();
-                let catcher = monkey.get_catcher(item);
-                let throw = (catcher, item);
-                throws.push(throw);
-            }
+        for _round in 0..10000 {
+            for monkey_id in 0..monkeys.len() {
+                let monkey = &mut monkeys[monkey_id];
+                let mut throws = vec![];
+                while monkey.has_items() {
+                    let item = monkey.inspect_item();
+                    let catcher = monkey.get_catcher(item);
+                    let throw = (catcher, item);
+                    throws.push(throw);
+                }
 
-            for (to, item) in throws {
-                let catcher = &mut monkeys[to];
-                catcher.catch_item(item % modulo);
+                for (to, item) in throws {
+                    let catcher = &mut monkeys[to];
+                    catcher.catch_item(item % modulo);
+                }
             }
         }
+
+        Ok(monkey_business_level(&monkeys))
     }
 
-    monkey_business_level(&monkeys)
+    const REFERENCES: [Option<ReferenceSolver>; 2] =
+        [Some(reference::part1), Some(reference::part2)];
+
+    /// `size` monkeys, at least 2. Operations that would make worry levels overflow during the
+    /// rounds of part 1 are replaced with additions.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        const DIVISORS: [u64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+        let count = size.max(2);
+        let mut monkeys = (0..count)
+            .map(|id| {
+                let others = (0..count).filter(|&other| other != id).collect::<Vec<_>>();
+                let if_true = *rng.choose(&others);
+                let if_false = match others.iter().filter(|&&other| other != if_true).count() {
+                    0 => if_true,
+                    choices => *others
+                        .iter()
+                        .filter(|&&other| other != if_true)
+                        .nth(rng.range(0..=choices - 1))
+                        .unwrap(),
+                };
+
+                GeneratedMonkey {
+                    items: (0..rng.range(1..=6))
+                        .map(|_| rng.range(50..=99) as u64)
+                        .collect(),
+                    operation: generate_operation(rng),
+                    test: *rng.choose(&DIVISORS),
+                    if_true,
+                    if_false,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        while let Some(monkey) = first_overflow(&monkeys) {
+            monkeys[monkey].operation = GeneratedOperation::Add(rng.range(1..=8) as u64);
+        }
+
+        let descriptions = monkeys
+            .iter()
+            .enumerate()
+            .map(|(id, monkey)| {
+                let items = monkey
+                    .items
+                    .iter()
+                    .map(u64::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!(
+                    "Monkey {id}:\n  \
+                     Starting items: {items}\n  \
+                     Operation: new = {}\n  \
+                     Test: divisible by {}\n    \
+                     If true: throw to monkey {}\n    \
+                     If false: throw to monkey {}\n",
+                    monkey.operation, monkey.test, monkey.if_true, monkey.if_false
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Some(descriptions.join("\n"))
+    }
 }
 
-fn monkey_business_level(monkeys: &Vec<Monkey>) -> PuzzleResult {
+fn monkey_business_level(monkeys: &[Monkey]) -> PuzzleResult {
     let mut sorted_inspections = monkeys
         .iter()
         .map(|monkey| monkey.inspections())
@@ -64,16 +144,136 @@ fn monkey_business_level(monkeys: &Vec<Monkey>) -> PuzzleResult {
 
     sorted_inspections.sort_by(|a, b| b.cmp(a));
 
-    let result = (sorted_inspections[0] * sorted_inspections[1]) as i64;
+    // widened, as the product of two u64 counts can overflow a u64
+    let result = u128::from(sorted_inspections[0]) * u128::from(sorted_inspections[1]);
 
     result.into()
 }
 
-fn transform_input(input: &str) -> Vec<Monkey> {
-    input
+fn transform_input(input: &str) -> Result<Vec<Monkey>, SolveError> {
+    let monkeys = input
         .split_terminator("\n\n")
-        .filter_map(|input| Monkey::from_str(input).ok())
-        .collect::<Vec<_>>()
+        .map(|monkey| Monkey::from_str(monkey).map_err(|err| err.relative_to(input, monkey)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if monkeys.len() < 2 {
+        return Err(SolveError::invalid_input(
+            "there should be at least two monkeys",
+        ));
+    }
+
+    if let Some(catcher) = monkeys
+        .iter()
+        .flat_map(|monkey| monkey.catchers())
+        .find(|&catcher| catcher >= monkeys.len())
+    {
+        return Err(SolveError::invalid_input(format!(
+            "monkey {catcher} to throw items to does not exist"
+        )));
+    }
+
+    Ok(monkeys)
+}
+
+fn lcm(a: u64, b: u64) -> u64 {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+
+    a / x * b
+}
+
+/// A monkey of a generated input.
+struct GeneratedMonkey {
+    items: Vec<u64>,
+    operation: GeneratedOperation,
+    test: u64,
+    if_true: usize,
+    if_false: usize,
+}
+
+enum GeneratedOperation {
+    Square,
+    Multiply(u64),
+    Add(u64),
+}
+
+impl GeneratedOperation {
+    fn exec(&self, item: u64) -> Option<u64> {
+        match self {
+            Self::Square => item.checked_mul(item),
+            Self::Multiply(operand) => item.checked_mul(*operand),
+            Self::Add(operand) => item.checked_add(*operand),
+        }
+    }
+}
+
+impl Display for GeneratedOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Square => write!(f, "old * old"),
+            Self::Multiply(operand) => write!(f, "old * {operand}"),
+            Self::Add(operand) => write!(f, "old + {operand}"),
+        }
+    }
+}
+
+fn generate_operation(rng: &mut Rng) -> GeneratedOperation {
+    match rng.range(0..=7) {
+        0 => GeneratedOperation::Square,
+        1..=3 => GeneratedOperation::Multiply(rng.range(2..=19) as u64),
+        _ => GeneratedOperation::Add(rng.range(1..=8) as u64),
+    }
+}
+
+/// Plays the rounds of part 1, returning the first monkey whose operation overflows.
+fn first_overflow(monkeys: &[GeneratedMonkey]) -> Option<usize> {
+    let mut items = monkeys
+        .iter()
+        .map(|monkey| monkey.items.clone())
+        .collect::<Vec<_>>();
+
+    for _round in 0..20 {
+        for (id, monkey) in monkeys.iter().enumerate() {
+            for item in mem::take(&mut items[id]) {
+                let Some(worry_level) = monkey.operation.exec(item) else {
+                    return Some(id);
+                };
+
+                let worry_level = worry_level / 3;
+                let catcher = if worry_level.is_multiple_of(monkey.test) {
+                    monkey.if_true
+                } else {
+                    monkey.if_false
+                };
+                items[catcher].push(worry_level);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod modulo_tests {
+    use aoc_framework::shared::Solution;
+
+    use super::{lcm, Day11};
+
+    #[test]
+    fn shared_divisors_are_counted_once() {
+        // sixteen monkeys all testing divisibility by 23
+        let monkeys = Day11::parse(include_str!("../inputs/day11/test2.txt")).unwrap();
+
+        let product = monkeys
+            .iter()
+            .try_fold(1u64, |acc, monkey| acc.checked_mul(monkey.test()));
+        let modulo = monkeys.iter().fold(1, |acc, monkey| lcm(acc, monkey.test()));
+
+        assert_eq!(product, None);
+        assert_eq!(modulo, 23);
+    }
 }
 
-test_solvers!(10605, 2713310158);
+test_solvers!(Day11);
@@ -1,5 +1,8 @@
-use std::str::FromStr;
+use std::{error::Error, fmt::Display, str::FromStr};
 
+use aoc_framework::diagnostic::Diagnostic;
+
+#[derive(Clone)]
 pub struct Monkey {
     _id: usize,
     items: Vec<u64>,
@@ -19,30 +22,29 @@ impl Monkey {
         self.inspections
     }
 
+    pub fn catchers(&self) -> [usize; 2] {
+        [self.if_true, self.if_false]
+    }
+
     pub fn has_items(&self) -> bool {
-        self.items.len() != 0
+        !self.items.is_empty()
     }
 
-    pub fn catch_item(&mut self, item: u64) -> () {
+    pub fn catch_item(&mut self, item: u64) {
         self.items.insert(0, item);
     }
 
     pub fn inspect_item(&mut self) -> u64 {
-        let item = self.start_inspection();
-
-        item
+        self.start_inspection()
     }
 
-    pub fn lower_worry_level(&self, item: u64) -> u64 {
-        let item = (item as f32) / 3.0;
-
-        item.floor() as u64
+    pub fn lower_worry_level(item: u64) -> u64 {
+        // integer division, as floats round large worry levels
+        item / 3
     }
 
     pub fn get_catcher(&mut self, item: u64) -> usize {
-        let throw_to_monkey = self.test_item(item);
-
-        throw_to_monkey
+        self.test_item(item)
     }
 
     fn start_inspection(&mut self) -> u64 {
@@ -60,7 +62,7 @@ impl Monkey {
     }
 
     fn test_item(&self, item: u64) -> usize {
-        if item % self.test == 0 {
+        if item.is_multiple_of(self.test) {
             self.if_true
         } else {
             self.if_false
@@ -70,79 +72,103 @@ impl Monkey {
 
 #[derive(Debug)]
 pub enum ParseMonkeyError {
-    MissingLineError,
+    /// The description ended before the line with the given shape.
+    MissingLineError(&'static str),
     InvalidIdError,
     InvalidStartingItemsError,
     InvalidOperationError,
     InvalidTestError,
 }
 
+impl Display for ParseMonkeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingLineError(expected) => {
+                write!(f, "monkey description is missing its '{expected}' line")
+            }
+            Self::InvalidIdError => write!(f, "expected a 'Monkey <id>:' line"),
+            Self::InvalidStartingItemsError => {
+                write!(f, "expected a 'Starting items: <items>' line")
+            }
+            Self::InvalidOperationError => {
+                write!(f, "expected an 'Operation: new = old <+|*> <operand>' line")
+            }
+            Self::InvalidTestError => write!(
+                f,
+                "expected a 'Test: divisible by <n>' line followed by 'If true/false: throw to monkey <id>' lines"
+            ),
+        }
+    }
+}
+
+impl Error for ParseMonkeyError {}
+
 impl FromStr for Monkey {
-    type Err = ParseMonkeyError;
+    type Err = Diagnostic<ParseMonkeyError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines();
+        let mut lines = s.lines().map(str::trim);
+        let mut next_line = |expected| {
+            lines.next().ok_or_else(|| {
+                Diagnostic::at_char(ParseMonkeyError::MissingLineError(expected), s, s.len())
+            })
+        };
 
-        let id_line = lines
-            .next()
-            .ok_or(ParseMonkeyError::MissingLineError)?
-            .trim();
+        let id_line = next_line("Monkey <id>:")?;
 
         let id: usize = id_line
             .strip_prefix("Monkey ")
             .and_then(|id| id.strip_suffix(":"))
             .and_then(|id| id.parse::<usize>().ok())
-            .ok_or(ParseMonkeyError::InvalidIdError)?;
+            .ok_or_else(|| Diagnostic::new(ParseMonkeyError::InvalidIdError, s, id_line))?;
 
-        let starting_items = lines
-            .next()
-            .ok_or(ParseMonkeyError::MissingLineError)?
-            .trim();
+        let starting_items_line = next_line("Starting items: <items>")?;
 
-        let starting_items = starting_items
+        let starting_items = starting_items_line
             .strip_prefix("Starting items: ")
-            .ok_or(ParseMonkeyError::InvalidStartingItemsError)?;
+            .ok_or_else(|| {
+                Diagnostic::new(
+                    ParseMonkeyError::InvalidStartingItemsError,
+                    s,
+                    starting_items_line,
+                )
+            })?;
 
         let starting_items = starting_items
             .split(", ")
-            .filter_map(|item| item.parse::<u64>().ok())
-            .collect::<Vec<_>>();
+            .filter(|item| !item.is_empty())
+            .map(|item| {
+                item.parse::<u64>().map_err(|_| {
+                    Diagnostic::new(ParseMonkeyError::InvalidStartingItemsError, s, item)
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
-        let operation = lines
-            .next()
-            .ok_or(ParseMonkeyError::MissingLineError)?
-            .trim();
-        let operation = Operation::from_str(operation)?;
+        let operation = next_line("Operation: new = <expression>")?;
+        let operation =
+            Operation::from_str(operation).map_err(|err| err.relative_to(s, operation))?;
 
-        let test = lines
-            .next()
-            .ok_or(ParseMonkeyError::MissingLineError)?
-            .trim();
+        let test_line = next_line("Test: divisible by <n>")?;
 
-        let test = test
+        let test = test_line
             .strip_prefix("Test: divisible by ")
             .and_then(|test| test.parse::<u64>().ok())
-            .ok_or(ParseMonkeyError::InvalidTestError)?;
+            .filter(|&test| test != 0)
+            .ok_or_else(|| Diagnostic::new(ParseMonkeyError::InvalidTestError, s, test_line))?;
 
-        let if_true = lines
-            .next()
-            .ok_or(ParseMonkeyError::MissingLineError)?
-            .trim();
+        let if_true_line = next_line("If true: throw to monkey <id>")?;
 
-        let if_true = if_true
+        let if_true = if_true_line
             .strip_prefix("If true: throw to monkey ")
             .and_then(|test| test.parse::<usize>().ok())
-            .ok_or(ParseMonkeyError::InvalidTestError)?;
+            .ok_or_else(|| Diagnostic::new(ParseMonkeyError::InvalidTestError, s, if_true_line))?;
 
-        let if_false = lines
-            .next()
-            .ok_or(ParseMonkeyError::MissingLineError)?
-            .trim();
+        let if_false_line = next_line("If false: throw to monkey <id>")?;
 
-        let if_false = if_false
+        let if_false = if_false_line
             .strip_prefix("If false: throw to monkey ")
             .and_then(|test| test.parse::<usize>().ok())
-            .ok_or(ParseMonkeyError::InvalidTestError)?;
+            .ok_or_else(|| Diagnostic::new(ParseMonkeyError::InvalidTestError, s, if_false_line))?;
 
         Ok(Monkey {
             _id: id,
@@ -157,37 +183,41 @@ impl FromStr for Monkey {
 }
 
 impl FromStr for Operation {
-    type Err = ParseMonkeyError;
+    type Err = Diagnostic<ParseMonkeyError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let operation = s
             .strip_prefix("Operation: new = ")
-            .ok_or(ParseMonkeyError::InvalidOperationError)?;
+            .ok_or_else(|| Diagnostic::new(ParseMonkeyError::InvalidOperationError, s, s))?;
 
         let parts = operation.split(' ').collect::<Vec<_>>();
 
-        match parts.as_slice() {
+        match *parts.as_slice() {
             ["old", "+", operand] => Ok(Self {
-                operand: Operand::from_str(operand)?,
+                operand: Operand::from_str(operand).map_err(|err| err.relative_to(s, operand))?,
                 operator: Self::add_operator,
             }),
             ["old", "*", operand] => Ok(Self {
-                operand: Operand::from_str(operand)?,
+                operand: Operand::from_str(operand).map_err(|err| err.relative_to(s, operand))?,
                 operator: Self::multiply_operator,
             }),
-            _ => Err(ParseMonkeyError::InvalidOperationError),
+            _ => Err(Diagnostic::new(
+                ParseMonkeyError::InvalidOperationError,
+                s,
+                operation,
+            )),
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Operand {
     Number(u64),
     Old,
 }
 
 impl FromStr for Operand {
-    type Err = ParseMonkeyError;
+    type Err = Diagnostic<ParseMonkeyError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -195,14 +225,14 @@ impl FromStr for Operand {
             num => {
                 let num = num
                     .parse::<u64>()
-                    .map_err(|_| ParseMonkeyError::InvalidOperationError)?;
+                    .map_err(|_| Diagnostic::new(ParseMonkeyError::InvalidOperationError, s, s))?;
                 Ok(Self::Number(num))
             }
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Operation {
     pub operator: fn(u64, u64) -> u64,
     operand: Operand,
@@ -224,3 +254,14 @@ impl Operation {
         a * b
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Monkey;
+
+    #[test]
+    fn worry_level_is_lowered_exactly() {
+        // as an f32, 16777221 rounds down to 16777220, a multiple of 3 below it
+        assert_eq!(Monkey::lower_worry_level(16777221), 5592407);
+    }
+}
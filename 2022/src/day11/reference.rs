@@ -0,0 +1,172 @@
+//! Reference solutions playing the rounds without the least common multiple: part 1 on exact
+//! worry levels, part 2 on the remainder of each worry level by the divisor of every monkey.
+//!
+//! Part 2 is intentionally slow, applying every operation once per monkey for each of the 10000
+//! rounds, which makes it the longest of the reference comparisons run by `cargo test`. Its rounds
+//! are not capped, as they have to match those of the solver.
+
+use aoc_framework::shared::{PuzzleResult, SolveError};
+
+struct Monkey {
+    items: Vec<u64>,
+    operation: Operation,
+    test: u64,
+    catchers: [usize; 2],
+}
+
+struct Operation {
+    multiply: bool,
+    /// Operands of the operation, `None` standing for the old worry level.
+    operands: [Option<u128>; 2],
+}
+
+impl Monkey {
+    fn catcher(&self, divisible: bool) -> usize {
+        if divisible {
+            self.catchers[0]
+        } else {
+            self.catchers[1]
+        }
+    }
+}
+
+pub fn part1(input: &str) -> Result<PuzzleResult, SolveError> {
+    let monkeys = monkeys(input)?;
+    let mut items = monkeys
+        .iter()
+        .map(|monkey| monkey.items.iter().map(|&item| u128::from(item)).collect())
+        .collect::<Vec<Vec<_>>>();
+    let mut inspections = vec![0; monkeys.len()];
+
+    for _round in 0..20 {
+        for (id, monkey) in monkeys.iter().enumerate() {
+            for item in std::mem::take(&mut items[id]) {
+                inspections[id] += 1;
+
+                let worry_level = apply(&monkey.operation, item)? / 3;
+                let divisible = worry_level % u128::from(monkey.test) == 0;
+                items[monkey.catcher(divisible)].push(worry_level);
+            }
+        }
+    }
+
+    monkey_business_level(inspections)
+}
+
+pub fn part2(input: &str) -> Result<PuzzleResult, SolveError> {
+    let monkeys = monkeys(input)?;
+    // remainders of each item by the divisor of each monkey
+    let mut items = monkeys
+        .iter()
+        .map(|monkey| {
+            monkey
+                .items
+                .iter()
+                .map(|&item| monkeys.iter().map(|other| item % other.test).collect())
+                .collect()
+        })
+        .collect::<Vec<Vec<Vec<u64>>>>();
+    let mut inspections = vec![0; monkeys.len()];
+
+    for _round in 0..10000 {
+        for (id, monkey) in monkeys.iter().enumerate() {
+            for mut item in std::mem::take(&mut items[id]) {
+                inspections[id] += 1;
+
+                for (remainder, other) in item.iter_mut().zip(&monkeys) {
+                    let worry_level = apply(&monkey.operation, u128::from(*remainder))?;
+                    *remainder = (worry_level % u128::from(other.test)) as u64;
+                }
+                let divisible = item[id] == 0;
+                items[monkey.catcher(divisible)].push(item);
+            }
+        }
+    }
+
+    monkey_business_level(inspections)
+}
+
+/// Worry level after the operation, an error when it overflows even a u128.
+fn apply(operation: &Operation, old: u128) -> Result<u128, SolveError> {
+    let [left, right] = operation.operands.map(|operand| operand.unwrap_or(old));
+
+    let new = if operation.multiply {
+        left.checked_mul(right)
+    } else {
+        left.checked_add(right)
+    };
+
+    new.ok_or_else(|| SolveError::invalid_input("worry level should not overflow"))
+}
+
+fn operation(expression: &str) -> Result<Operation, SolveError> {
+    let operand = |operand: &str| match operand {
+        "old" => Ok(None),
+        number => number.parse::<u128>().map(Some).map_err(SolveError::from),
+    };
+
+    match *expression.split_whitespace().collect::<Vec<_>>() {
+        [left, operator @ ("+" | "*"), right] => Ok(Operation {
+            multiply: operator == "*",
+            operands: [operand(left)?, operand(right)?],
+        }),
+        _ => Err(SolveError::invalid_input(
+            "expected an 'old <+|*> <operand>' operation",
+        )),
+    }
+}
+
+fn monkey_business_level(mut inspections: Vec<u128>) -> Result<PuzzleResult, SolveError> {
+    inspections.sort_by(|a, b| b.cmp(a));
+
+    match inspections[..] {
+        [first, second, ..] => Ok((first * second).into()),
+        _ => Err(SolveError::invalid_input(
+            "there should be at least two monkeys",
+        )),
+    }
+}
+
+fn monkeys(input: &str) -> Result<Vec<Monkey>, SolveError> {
+    let monkeys = input
+        .split_terminator("\n\n")
+        .map(|description| {
+            let lines = description.lines().map(str::trim).collect::<Vec<_>>();
+            let field = |index: usize, prefix: &str| {
+                lines
+                    .get(index)
+                    .and_then(|line| line.strip_prefix(prefix))
+                    .ok_or_else(|| SolveError::invalid_input(format!("expected a '{prefix}' line")))
+            };
+
+            Ok(Monkey {
+                items: field(1, "Starting items:")?
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()?,
+                operation: operation(field(2, "Operation: new =")?)?,
+                test: field(3, "Test: divisible by ")?
+                    .parse()
+                    .ok()
+                    .filter(|&test| test != 0)
+                    .ok_or_else(|| SolveError::invalid_input("divisor should not be zero"))?,
+                catchers: [
+                    field(4, "If true: throw to monkey ")?.parse()?,
+                    field(5, "If false: throw to monkey ")?.parse()?,
+                ],
+            })
+        })
+        .collect::<Result<Vec<_>, SolveError>>()?;
+
+    let missing_catcher = monkeys
+        .iter()
+        .flat_map(|monkey| monkey.catchers)
+        .any(|catcher| catcher >= monkeys.len());
+    if monkeys.len() < 2 || missing_catcher {
+        return Err(SolveError::invalid_input("monkeys should throw to each other"));
+    }
+
+    Ok(monkeys)
+}
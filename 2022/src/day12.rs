@@ -1,88 +1,158 @@
-mod dijkstra;
+pub mod dijkstra;
+pub mod reference;
 
 use std::str::FromStr;
 
-use crate::{shared::PuzzleResult, test_solvers};
+use aoc_framework::{
+    generate::Rng,
+    shared::{PuzzleResult, ReferenceSolver, Solution, SolveError},
+    test_solvers,
+};
 
 use dijkstra::Heightmap;
 
 use self::dijkstra::Position;
 
-pub fn part1(input: &str) -> PuzzleResult {
-    let (start_position, end_position, heightmap) = transform_input_for_part_1(input);
+/// The heightmap along with the positions the climb can start and end at.
+pub struct HillClimb {
+    heightmap: Heightmap,
+    start_position: Position,
+    /// The start position and every square of elevation `a`.
+    lowest_positions: Vec<Position>,
+    end_position: Position,
+}
 
-    let start_node = heightmap
-        .get(&start_position)
-        .expect("start node should exist");
+pub struct Day12;
 
-    let distance_from_start_to_end = heightmap.distances_to(end_position)[start_node] as i64;
+impl Solution for Day12 {
+    type Parsed = HillClimb;
 
-    distance_from_start_to_end.into()
-}
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        transform_input(input)
+    }
 
-pub fn part2(input: &str) -> PuzzleResult {
-    let (start_positions, end_position, heightmap) = transform_input_for_part_2(input);
+    fn part1(hill_climb: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        let heightmap = &hill_climb.heightmap;
 
-    let distances_to_end = heightmap.distances_to(end_position);
+        let start_node = heightmap
+            .get(&hill_climb.start_position)
+            .expect("start node should exist");
 
-    let min_distance = distances_to_end
-        .iter()
-        .filter(|(&node, _distance)| start_positions.contains(&node.position()))
-        .map(|(_node, distance)| distance)
-        .min()
-        .unwrap();
+        let distance_from_start_to_end = *heightmap
+            .distances_to(hill_climb.end_position)
+            .get(start_node)
+            .ok_or_else(|| SolveError::invalid_input("there should be a path from S to E"))?;
 
-    (*min_distance as i64).into()
-}
+        Ok(distance_from_start_to_end.into())
+    }
 
-fn transform_input_for_part_1(input: &str) -> (Position, Position, Heightmap) {
-    let mut start_position = None;
-    let mut end_position = None;
+    fn part2(hill_climb: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        let distances_to_end = hill_climb.heightmap.distances_to(hill_climb.end_position);
+
+        let min_distance = distances_to_end
+            .iter()
+            .filter(|(&node, _distance)| hill_climb.lowest_positions.contains(&node.position()))
+            .map(|(_node, distance)| distance)
+            .min()
+            .ok_or_else(|| {
+                SolveError::invalid_input(
+                    "there should be a path from a square of elevation a to E",
+                )
+            })?;
+
+        Ok((*min_distance).into())
+    }
 
-    'outer: for (row, line) in input.lines().enumerate() {
-        for (column, c) in line.chars().enumerate() {
-            if start_position.is_some() && end_position.is_some() {
-                break 'outer;
-            }
-            if c == 'S' {
-                start_position = Some(Position::new(column as i32, row as i32))
-            } else if c == 'E' {
-                end_position = Some(Position::new(column as i32, row as i32))
-            }
+    const REFERENCES: [Option<ReferenceSolver>; 2] =
+        [Some(reference::part1), Some(reference::part2)];
+
+    /// A heightmap of `size` by `size` squares, at least 30, rising from the start in the top left
+    /// corner to the end in the bottom right one. A path climbing one elevation at a time links
+    /// them.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let size = size.max(30);
+        let corner = size / 4;
+
+        // gentle slope with bumps
+        let mut heights = (0..size)
+            .map(|row| {
+                (0..size)
+                    .map(|column| {
+                        let height = (row + column) * 25 / (2 * (size - 1)) + rng.range(0..=4);
+                        height.saturating_sub(2).min(25)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let start = (rng.range(0..=corner), rng.range(0..=corner));
+        let end = (
+            size - 1 - rng.range(0..=corner),
+            size - 1 - rng.range(0..=corner),
+        );
+
+        // steps right and down in random order, long enough to climb from a to z
+        let mut steps = [(1, 0)]
+            .repeat(end.0 - start.0)
+            .into_iter()
+            .chain([(0, 1)].repeat(end.1 - start.1))
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut steps);
+
+        let (mut column, mut row) = start;
+        for (step, (column_step, row_step)) in steps.iter().enumerate() {
+            heights[row][column] = step * 25 / steps.len();
+            column += column_step;
+            row += row_step;
         }
-    }
 
-    let start_position = start_position.expect("input should have a start position");
-    let end_position = end_position.expect("input should have an end position");
+        let mut heightmap = String::new();
+        for (row, row_heights) in heights.iter().enumerate() {
+            for (column, &height) in row_heights.iter().enumerate() {
+                heightmap.push(match (column, row) {
+                    position if position == start => 'S',
+                    position if position == end => 'E',
+                    _ => (b'a' + height as u8) as char,
+                });
+            }
+            heightmap.push('\n');
+        }
 
-    (
-        start_position,
-        end_position,
-        Heightmap::from_str(input).expect("input should be a valid heightmap"),
-    )
+        Some(heightmap)
+    }
 }
 
-fn transform_input_for_part_2(input: &str) -> (Vec<Position>, Position, Heightmap) {
-    let mut start_positions = vec![];
+fn transform_input(input: &str) -> Result<HillClimb, SolveError> {
+    let mut start_position = None;
+    let mut lowest_positions = vec![];
     let mut end_position = None;
 
     for (row, line) in input.lines().enumerate() {
         for (column, c) in line.chars().enumerate() {
-            if c == 'S' || c == 'a' {
-                start_positions.push(Position::new(column as i32, row as i32))
+            let position = Position::new(column as i32, row as i32);
+
+            if c == 'S' {
+                start_position = Some(position);
+                lowest_positions.push(position);
+            } else if c == 'a' {
+                lowest_positions.push(position);
             } else if c == 'E' {
-                end_position = Some(Position::new(column as i32, row as i32))
+                end_position = Some(position);
             }
         }
     }
 
-    let end_position = end_position.expect("input should have an end position");
+    let start_position = start_position
+        .ok_or_else(|| SolveError::invalid_input("input should have a start position"))?;
+    let end_position = end_position
+        .ok_or_else(|| SolveError::invalid_input("input should have an end position"))?;
 
-    (
-        start_positions,
+    Ok(HillClimb {
+        heightmap: Heightmap::from_str(input)?,
+        start_position,
+        lowest_positions,
         end_position,
-        Heightmap::from_str(input).expect("input should be a valid heightmap"),
-    )
+    })
 }
 
-test_solvers!(31, 29);
+test_solvers!(Day12);
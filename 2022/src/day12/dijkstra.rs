@@ -1,9 +1,13 @@
 use std::{
     cmp::Reverse,
     collections::{BinaryHeap, HashMap, HashSet},
+    error::Error,
+    fmt::Display,
     str::FromStr,
 };
 
+use aoc_framework::diagnostic::Diagnostic;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Position {
     x: i32,
@@ -89,7 +93,7 @@ impl Heightmap {
                     continue;
                 }
                 let new_distance = distance_to_node + 1;
-                if new_distance < *distance_map.get(neighbor).unwrap_or(&std::u32::MAX) {
+                if new_distance < *distance_map.get(neighbor).unwrap_or(&u32::MAX) {
                     distance_map.insert(neighbor, new_distance);
                     min_distance_heap.push((Reverse(new_distance), neighbor))
                 }
@@ -99,41 +103,59 @@ impl Heightmap {
         distance_map
     }
 
-    fn char_to_height(c: char) -> u8 {
+    fn char_to_height(c: char) -> Option<u8> {
         const LOWERCASE_A_ASCII_VALUE: u8 = 97;
         let c = match c {
             'S' => 'a',
             'E' => 'z',
-            c => c,
+            c if c.is_ascii_lowercase() => c,
+            _ => return None,
         };
 
         let ascii_value = c as u8;
-        ascii_value - LOWERCASE_A_ASCII_VALUE
+        Some(ascii_value - LOWERCASE_A_ASCII_VALUE)
     }
 }
 
 #[derive(Debug)]
-pub struct ParseHeightmapError;
+pub struct ParseHeightmapError(char);
+
+impl Display for ParseHeightmapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expected an elevation from a to z, S or E, got '{}'",
+            self.0
+        )
+    }
+}
+
+impl Error for ParseHeightmapError {}
 
 impl FromStr for Heightmap {
-    type Err = ParseHeightmapError;
+    type Err = Diagnostic<ParseHeightmapError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let nodes = s
             .lines()
             .enumerate()
             .map(|(row, line)| {
-                line.chars()
+                line.char_indices()
                     .enumerate()
-                    .map(|(column, c)| {
-                        Node::new(
-                            Position::new(column as i32, row as i32),
-                            Self::char_to_height(c),
-                        )
+                    .map(|(column, (offset, c))| {
+                        let height = Self::char_to_height(c).ok_or_else(|| {
+                            Diagnostic::new(
+                                ParseHeightmapError(c),
+                                s,
+                                &line[offset..offset + c.len_utf8()],
+                            )
+                        })?;
+
+                        Ok(Node::new(Position::new(column as i32, row as i32), height))
                     })
                     .collect()
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
         Ok(Self::new(nodes))
     }
@@ -0,0 +1,91 @@
+//! Reference solutions walking forward from the start squares with a breadth-first search, one
+//! search per start square in part 2.
+
+use std::collections::VecDeque;
+
+use aoc_framework::shared::{PuzzleResult, SolveError};
+
+pub fn part1(input: &str) -> Result<PuzzleResult, SolveError> {
+    let grid = grid(input);
+
+    starts(&grid, b"S")
+        .into_iter()
+        .find_map(|start| steps_to_end(&grid, start))
+        .map(PuzzleResult::from)
+        .ok_or_else(|| SolveError::invalid_input("there should be a path from S to E"))
+}
+
+pub fn part2(input: &str) -> Result<PuzzleResult, SolveError> {
+    let grid = grid(input);
+
+    starts(&grid, b"Sa")
+        .into_iter()
+        .filter_map(|start| steps_to_end(&grid, start))
+        .min()
+        .map(PuzzleResult::from)
+        .ok_or_else(|| SolveError::invalid_input("there should be a path from a square a to E"))
+}
+
+fn grid(input: &str) -> Vec<&[u8]> {
+    input.lines().map(str::as_bytes).collect()
+}
+
+/// Positions of the squares among `squares`.
+fn starts(grid: &[&[u8]], squares: &[u8]) -> Vec<(usize, usize)> {
+    let mut starts = vec![];
+    for (row, line) in grid.iter().enumerate() {
+        for (column, square) in line.iter().enumerate() {
+            if squares.contains(square) {
+                starts.push((column, row));
+            }
+        }
+    }
+
+    starts
+}
+
+fn elevation(square: u8) -> u8 {
+    match square {
+        b'S' => b'a',
+        b'E' => b'z',
+        square => square,
+    }
+}
+
+/// Fewest steps from the start to E, each step climbing at most one elevation.
+fn steps_to_end(grid: &[&[u8]], start: (usize, usize)) -> Option<usize> {
+    let mut steps = grid
+        .iter()
+        .map(|line| vec![None; line.len()])
+        .collect::<Vec<_>>();
+    steps[start.1][start.0] = Some(0);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some((column, row)) = queue.pop_front() {
+        let square_steps = steps[row][column]?;
+        if grid[row][column] == b'E' {
+            return Some(square_steps);
+        }
+
+        let neighbors = [
+            (column.wrapping_sub(1), row),
+            (column + 1, row),
+            (column, row.wrapping_sub(1)),
+            (column, row + 1),
+        ];
+        for (next_column, next_row) in neighbors {
+            let Some(&next) = grid.get(next_row).and_then(|line| line.get(next_column)) else {
+                continue;
+            };
+
+            if elevation(next) <= elevation(grid[row][column]) + 1
+                && steps[next_row][next_column].is_none()
+            {
+                steps[next_row][next_column] = Some(square_steps + 1);
+                queue.push_back((next_column, next_row));
+            }
+        }
+    }
+
+    None
+}
@@ -1,68 +1,146 @@
-mod packet;
+pub mod packet;
 
 use std::str::FromStr;
 
-use crate::{shared::PuzzleResult, test_solvers};
-
-use self::packet::Packet;
-
-pub fn part1(input: &str) -> PuzzleResult {
-    let packet_pairs = transform_input_for_part_1(input);
-
-    let packets_in_right_order = packet_pairs
-        .iter()
-        .enumerate()
-        .map(|(index, (left, right))| (index + 1, left.cmp(right)))
-        .filter(|(_, ord)| ord.is_lt());
-
-    let pairs_in_right_order_indices_sum = packets_in_right_order
-        .map(|(index, _)| index)
-        .sum::<usize>();
-
-    (pairs_in_right_order_indices_sum as i64).into()
-}
-
-pub fn part2(input: &str) -> PuzzleResult {
-    let mut packets = transform_input_for_part_2(input);
-
-    let divider_packets = [
-        Packet::from_str("[[2]]").unwrap(),
-        Packet::from_str("[[6]]").unwrap(),
-    ];
-
-    packets.extend(divider_packets.iter().cloned());
-
-    packets.sort();
-
-    let result = packets
-        .iter()
-        .enumerate()
-        .filter(|(_, packet)| divider_packets.contains(packet))
-        .fold(1, |acc, (index, _)| acc * (index + 1)) as i64;
-
-    result.into()
+use aoc_framework::{
+    generate::Rng,
+    diagnostic::Diagnostic,
+    shared::{PuzzleResult, Solution, SolveError},
+    test_solvers,
+};
+
+use self::packet::{Packet, PacketValue};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed = Vec<(Packet, Packet)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        transform_input(input)
+    }
+
+    fn part1(packet_pairs: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        let packets_in_right_order = packet_pairs
+            .iter()
+            .enumerate()
+            .map(|(index, (left, right))| (index + 1, left.cmp(right)))
+            .filter(|(_, ord)| ord.is_lt());
+
+        let pairs_in_right_order_indices_sum = packets_in_right_order
+            .map(|(index, _)| index)
+            .sum::<usize>();
+
+        Ok(pairs_in_right_order_indices_sum.into())
+    }
+
+    fn part2(packet_pairs: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        let divider_packets = [Packet::from_str("[[2]]")?, Packet::from_str("[[6]]")?];
+
+        let mut packets = packet_pairs
+            .iter()
+            .flat_map(|(left, right)| [left, right])
+            .chain(&divider_packets)
+            .collect::<Vec<_>>();
+
+        packets.sort();
+
+        let result = packets
+            .iter()
+            .enumerate()
+            .filter(|(_, packet)| divider_packets.contains(packet))
+            .fold(1, |acc, (index, _)| acc * (index + 1));
+
+        Ok(result.into())
+    }
+
+    /// `size` pairs of packets, nested deeper as `size` grows. Right packets often differ from
+    /// their left packet deep inside only.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let max_depth = (4 + size / 20).min(1000);
+
+        let pairs = (0..size.max(1))
+            .map(|_| {
+                let depth = rng.range(0..=max_depth);
+                let left = generate_packet_value(rng, depth);
+                let right = if rng.ratio(1, 2) {
+                    let mut right = left.clone();
+                    mutate_packet_value(rng, &mut right);
+                    right
+                } else {
+                    let depth = rng.range(0..=max_depth);
+                    generate_packet_value(rng, depth)
+                };
+
+                format!("{left}\n{right}\n")
+            })
+            .collect::<Vec<_>>();
+
+        Some(pairs.join("\n"))
+    }
 }
 
-fn transform_input_for_part_1(input: &str) -> Vec<(Packet, Packet)> {
+fn transform_input(input: &str) -> Result<Vec<(Packet, Packet)>, SolveError> {
     input
         .split("\n\n")
         .map(|pairs| {
-            let pairs = pairs.lines().collect::<Vec<_>>();
-
-            let left_packet = Packet::from_str(pairs[0]).expect("first packet should be valid");
-            let right_packet = Packet::from_str(pairs[1]).expect("second packet should be valid");
+            let [left, right] = pairs.lines().collect::<Vec<_>>()[..] else {
+                return Err(Diagnostic::new("expected a pair of packets", input, pairs).into());
+            };
 
-            (left_packet, right_packet)
+            Ok((parse_packet(input, left)?, parse_packet(input, right)?))
         })
         .collect()
 }
 
-fn transform_input_for_part_2(input: &str) -> Vec<Packet> {
-    input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|packet| Packet::from_str(packet).expect("packet should be valid"))
-        .collect()
+fn parse_packet(input: &str, packet: &str) -> Result<Packet, SolveError> {
+    Ok(Packet::from_str(packet).map_err(|err| err.relative_to(input, packet))?)
+}
+
+/// A list nested `depth` times along one of its values, the others being integers or flat lists.
+fn generate_packet_value(rng: &mut Rng, depth: usize) -> PacketValue {
+    let len = rng.range(usize::from(depth > 0)..=4);
+    let nested_index = (depth > 0).then(|| rng.range(0..=len - 1));
+
+    PacketValue::List(
+        (0..len)
+            .map(|index| {
+                if Some(index) == nested_index {
+                    generate_packet_value(rng, depth - 1)
+                } else if rng.ratio(1, 5) {
+                    generate_packet_value(rng, 0)
+                } else {
+                    PacketValue::Int(rng.range(0..=10) as u32)
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Changes a list somewhere along a random path of nested lists.
+fn mutate_packet_value(rng: &mut Rng, value: &mut PacketValue) {
+    let PacketValue::List(list) = value else {
+        return;
+    };
+
+    let nested = list
+        .iter()
+        .enumerate()
+        .filter(|(_, value)| matches!(value, PacketValue::List(_)))
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+
+    if !nested.is_empty() && rng.ratio(3, 4) {
+        let index = *rng.choose(&nested);
+        mutate_packet_value(rng, &mut list[index]);
+    } else if !list.is_empty() && rng.ratio(1, 2) {
+        let index = rng.range(0..=list.len() - 1);
+        list[index] = PacketValue::Int(rng.range(0..=10) as u32);
+    } else if !list.is_empty() && rng.ratio(1, 2) {
+        list.pop();
+    } else {
+        list.push(PacketValue::Int(rng.range(0..=10) as u32));
+    }
 }
 
-test_solvers!(13, 140);
+test_solvers!(Day13);
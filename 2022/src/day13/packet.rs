@@ -1,10 +1,14 @@
 use std::{
     cmp::{self, Ordering},
+    error::Error,
     fmt::Display,
+    num::ParseIntError,
     str::FromStr,
     vec,
 };
 
+use aoc_framework::diagnostic::Diagnostic;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PacketValue {
     Int(u32),
@@ -19,10 +23,10 @@ impl Ord for PacketValue {
                 cmp_packet_list(left_list, right_list)
             }
             (&Self::Int(left_int), Self::List(right_list)) => {
-                cmp_packet_list(&vec![Self::Int(left_int)], right_list)
+                cmp_packet_list(&[Self::Int(left_int)], right_list)
             }
             (Self::List(left_list), &Self::Int(right_int)) => {
-                cmp_packet_list(left_list, &vec![Self::Int(right_int)])
+                cmp_packet_list(left_list, &[Self::Int(right_int)])
             }
         }
     }
@@ -34,7 +38,7 @@ impl PartialOrd for PacketValue {
     }
 }
 
-fn cmp_packet_list(left_list: &Vec<PacketValue>, right_list: &Vec<PacketValue>) -> Ordering {
+fn cmp_packet_list(left_list: &[PacketValue], right_list: &[PacketValue]) -> Ordering {
     let max_len = cmp::max(left_list.len(), right_list.len());
     for index in 0..max_len {
         let left_value = left_list.get(index);
@@ -78,7 +82,31 @@ impl Display for PacketValue {
 }
 
 #[derive(Debug)]
-pub struct ParsePacketValueError;
+pub enum ParsePacketValueError {
+    EmptyPacketError,
+    UnexpectedCharError(char),
+    InvalidIntegerError(ParseIntError),
+    ValueOutsideListError,
+    UnmatchedBracketError,
+    UnclosedListError,
+    TrailingCharactersError,
+}
+
+impl Display for ParsePacketValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyPacketError => write!(f, "expected a packet starting with '['"),
+            Self::UnexpectedCharError(c) => write!(f, "unexpected '{c}' in packet"),
+            Self::InvalidIntegerError(err) => write!(f, "invalid integer: {err}"),
+            Self::ValueOutsideListError => write!(f, "integer should be inside a list"),
+            Self::UnmatchedBracketError => write!(f, "']' does not close any list"),
+            Self::UnclosedListError => write!(f, "expected ']' to close the list"),
+            Self::TrailingCharactersError => write!(f, "unexpected characters after the packet"),
+        }
+    }
+}
+
+impl Error for ParsePacketValueError {}
 
 impl From<u32> for PacketValue {
     fn from(v: u32) -> Self {
@@ -87,68 +115,87 @@ impl From<u32> for PacketValue {
 }
 
 impl FromStr for PacketValue {
-    type Err = ParsePacketValueError;
+    type Err = Diagnostic<ParsePacketValueError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut current_list: Option<Vec<PacketValue>> = None;
         let mut list_stack = vec![];
 
-        let chars = s.chars().collect::<Vec<_>>();
-        let mut input = chars.as_slice();
+        let tokens = s.char_indices().collect::<Vec<_>>();
+        let mut input = tokens.as_slice();
+
+        while let [(offset, token), rest @ ..] = input {
+            let offset = *offset;
 
-        while input.len() != 0 {
-            match input {
-                ['[', rest @ ..] => {
+            match token {
+                '[' => {
                     if let Some(list) = current_list {
                         list_stack.push(list);
                     }
                     current_list = Some(vec![]);
                     input = rest;
                 }
-                [value, rest @ ..] if value.is_digit(10) => {
-                    let extra_digits = rest
-                        .iter()
-                        .take_while(|c| c.is_digit(10))
-                        .collect::<Vec<_>>();
-                    let number_of_extra_digits = extra_digits.len();
+                digit if digit.is_ascii_digit() => {
+                    let number_of_extra_digits =
+                        rest.iter().take_while(|(_, c)| c.is_ascii_digit()).count();
 
-                    let digits = [&[value], extra_digits.as_slice()].concat();
+                    // ASCII digits take a single byte each
+                    let digits = &s[offset..offset + 1 + number_of_extra_digits];
 
-                    let value = digits
-                        .iter()
-                        .map(|digit| digit.to_digit(10).unwrap())
-                        .fold(0, |value, digit| 10 * value + digit);
+                    let value = digits.parse().map_err(|err| {
+                        Diagnostic::new(ParsePacketValueError::InvalidIntegerError(err), s, digits)
+                    })?;
 
                     current_list
                         .as_mut()
-                        .ok_or(ParsePacketValueError)?
+                        .ok_or_else(|| {
+                            Diagnostic::new(ParsePacketValueError::ValueOutsideListError, s, digits)
+                        })?
                         .push(Self::Int(value));
 
                     input = &rest[number_of_extra_digits..];
                 }
-                [']'] if list_stack.is_empty() => {
-                    let complete_list = current_list.ok_or(ParsePacketValueError)?;
-                    return Ok(Self::List(complete_list));
-                }
-                [']', rest @ ..] => {
-                    let complete_list = current_list.ok_or(ParsePacketValueError)?;
-
-                    let mut parent_list = list_stack.pop().ok_or(ParsePacketValueError)?;
-                    parent_list.push(Self::List(complete_list));
-                    current_list = Some(parent_list);
+                ']' => {
+                    let complete_list = current_list.ok_or_else(|| {
+                        Diagnostic::at_char(ParsePacketValueError::UnmatchedBracketError, s, offset)
+                    })?;
+
+                    match (list_stack.pop(), rest) {
+                        (None, []) => return Ok(Self::List(complete_list)),
+                        (None, [(trailing_offset, _), ..]) => {
+                            return Err(Diagnostic::new(
+                                ParsePacketValueError::TrailingCharactersError,
+                                s,
+                                &s[*trailing_offset..],
+                            ))
+                        }
+                        (Some(mut parent_list), _) => {
+                            parent_list.push(Self::List(complete_list));
+                            current_list = Some(parent_list);
+                        }
+                    }
 
                     input = rest;
                 }
-                [',', rest @ ..] => {
+                ',' => {
                     input = rest;
                 }
-                _ => {
-                    return Err(ParsePacketValueError);
+                &token => {
+                    return Err(Diagnostic::at_char(
+                        ParsePacketValueError::UnexpectedCharError(token),
+                        s,
+                        offset,
+                    ));
                 }
             };
         }
 
-        Err(ParsePacketValueError)
+        let kind = match current_list {
+            Some(_) => ParsePacketValueError::UnclosedListError,
+            None => ParsePacketValueError::EmptyPacketError,
+        };
+
+        Err(Diagnostic::at_char(kind, s, s.len()))
     }
 }
 
@@ -185,7 +232,7 @@ impl Display for Packet {
 }
 
 impl FromStr for Packet {
-    type Err = ParsePacketValueError;
+    type Err = Diagnostic<ParsePacketValueError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let value = PacketValue::from_str(s)?;
@@ -1,65 +1,98 @@
 use std::str::FromStr;
 
-use crate::{shared::PuzzleResult, test_solvers};
+use aoc_framework::{
+    generate::Rng,
+    shared::{PuzzleResult, Solution, SolveError},
+    test_solvers,
+};
 
-mod rps;
+pub mod rps;
 
-pub fn part1(input: &str) -> PuzzleResult {
-    let moves = transform_input_for_part1(input);
-
-    moves
-        .iter()
-        .map(|(their_move, my_move)| {
-            let move_score: i64 = my_move.score().into();
-            let result_score: i64 = my_move.get_result(their_move).score().into();
-            (move_score + result_score) as i64
-        })
-        .sum::<i64>()
-        .into()
+/// A line of the strategy guide. The second column is read as a move in part 1 and as the
+/// expected result of the round in part 2.
+pub struct Round {
+    their_move: rps::Move,
+    my_move: rps::Move,
+    expected_result: rps::GameResult,
 }
 
-pub fn part2(input: &str) -> PuzzleResult {
-    let moves = transform_input_for_part2(input);
+pub struct Day2;
 
-    moves
-        .iter()
-        .map(|(their_move, expected_result)| {
-            let my_move = match expected_result {
-                rps::GameResult::Win => their_move.loses_with(),
-                rps::GameResult::Draw => their_move.draws_with(),
-                rps::GameResult::Lose => their_move.wins_with(),
-            };
-            let move_score = my_move.score();
-            let result_score = my_move.get_result(their_move).score();
-            (move_score + result_score) as i64
-        })
-        .sum::<i64>()
-        .into()
+impl Solution for Day2 {
+    type Parsed = Vec<Round>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        transform_input(input)
+    }
+
+    fn part1(rounds: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        Ok(rounds
+            .iter()
+            .map(|round| {
+                let move_score: i64 = round.my_move.score().into();
+                let result_score: i64 = round.my_move.get_result(&round.their_move).score().into();
+                move_score + result_score
+            })
+            .sum::<i64>()
+            .into())
+    }
+
+    fn part2(rounds: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        Ok(rounds
+            .iter()
+            .map(|round| {
+                let their_move = &round.their_move;
+                let my_move = match round.expected_result {
+                    rps::GameResult::Win => their_move.loses_with(),
+                    rps::GameResult::Draw => their_move.draws_with(),
+                    rps::GameResult::Lose => their_move.wins_with(),
+                };
+                let move_score = my_move.score();
+                let result_score = my_move.get_result(their_move).score();
+                (move_score + result_score) as i64
+            })
+            .sum::<i64>()
+            .into())
+    }
+
+    /// `size` rounds.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(
+            (0..size.max(1))
+                .map(|_| {
+                    let their_move = rng.choose(&["A", "B", "C"]);
+                    format!("{their_move} {}\n", rng.choose(&["X", "Y", "Z"]))
+                })
+                .collect(),
+        )
+    }
 }
 
-fn transform_input_for_part1(input: &str) -> Vec<(rps::Move, rps::Move)> {
+fn transform_input(input: &str) -> Result<Vec<Round>, SolveError> {
     input
         .lines()
-        .map(|moves| {
-            let moves: Vec<&str> = moves.split(' ').collect();
-            let their_move = rps::Move::from_str(moves[0]).expect("their move should be valid");
-            let my_move = rps::Move::from_str(moves[1]).expect("my move should be valid");
-            (their_move, my_move)
+        .map(|round| {
+            let (their_move, second_column) = split_columns(round);
+            let their_move = rps::Move::from_str(their_move)
+                .map_err(|err| err.relative_to(input, their_move))?;
+            let expected_result = rps::GameResult::from_str(second_column)
+                .map_err(|err| err.relative_to(input, second_column))?;
+            let my_move = rps::Move::from_str(second_column)
+                .map_err(|err| err.relative_to(input, second_column))?;
+
+            Ok(Round {
+                their_move,
+                my_move,
+                expected_result,
+            })
         })
         .collect()
 }
 
-fn transform_input_for_part2(input: &str) -> Vec<(rps::Move, rps::GameResult)> {
-    input
-        .lines()
-        .map(|game| {
-            let game: Vec<&str> = game.split(' ').collect();
-            let their_move = rps::Move::from_str(game[0]).expect("their move should be valid");
-            let expected_result =
-                rps::GameResult::from_str(game[1]).expect("expected game result should be valid");
-            (their_move, expected_result)
-        })
-        .collect()
+/// Splits a line into its two columns, leaving the second one empty (at the end of the line)
+/// when it is missing.
+fn split_columns(line: &str) -> (&str, &str) {
+    line.split_once(' ').unwrap_or((line, &line[line.len()..]))
 }
 
-test_solvers!(15, 12);
+test_solvers!(Day2);
@@ -1,4 +1,6 @@
-use std::str::FromStr;
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use aoc_framework::diagnostic::Diagnostic;
 
 #[derive(Debug)]
 pub enum GameResult {
@@ -18,7 +20,15 @@ impl GameResult {
 }
 
 #[derive(Debug, Clone)]
-pub struct ParseGameResultError;
+pub struct ParseGameResultError(String);
+
+impl Display for ParseGameResultError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected game result X, Y or Z, got '{}'", self.0)
+    }
+}
+
+impl Error for ParseGameResultError {}
 
 impl FromStr for GameResult {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -26,11 +36,11 @@ impl FromStr for GameResult {
             "X" => Ok(Self::Lose),
             "Y" => Ok(Self::Draw),
             "Z" => Ok(Self::Win),
-            _ => Err(ParseGameResultError),
+            _ => Err(Diagnostic::new(ParseGameResultError(s.to_owned()), s, s)),
         }
     }
 
-    type Err = ParseGameResultError;
+    type Err = Diagnostic<ParseGameResultError>;
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -76,12 +86,20 @@ impl Move {
         if self.wins_with() == *other {
             return GameResult::Win;
         }
-        return GameResult::Lose;
+        GameResult::Lose
     }
 }
 
 #[derive(Debug, Clone)]
-pub struct ParseMoveError;
+pub struct ParseMoveError(String);
+
+impl Display for ParseMoveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected move A, B, C, X, Y or Z, got '{}'", self.0)
+    }
+}
+
+impl Error for ParseMoveError {}
 
 impl FromStr for Move {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -89,9 +107,9 @@ impl FromStr for Move {
             "A" | "X" => Ok(Self::Rock),
             "B" | "Y" => Ok(Self::Paper),
             "C" | "Z" => Ok(Self::Scissors),
-            _ => Err(ParseMoveError),
+            _ => Err(Diagnostic::new(ParseMoveError(s.to_owned()), s, s)),
         }
     }
 
-    type Err = ParseMoveError;
+    type Err = Diagnostic<ParseMoveError>;
 }
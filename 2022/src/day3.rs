@@ -1,37 +1,80 @@
 use std::collections::HashSet;
 
-use crate::{shared::PuzzleResult, test_solvers};
+use aoc_framework::{
+    generate::Rng,
+    shared::{PuzzleResult, Solution, SolveError},
+    test_solvers,
+};
 
-pub fn part1(input: &str) -> PuzzleResult {
-    let rucksack = transform_input_for_part1(input);
+pub struct Day3;
 
-    rucksack
-        .iter()
-        .map(|compartments| {
-            let common_item = get_common_item(compartments)
-                .expect("rucksack compartments should have a common item");
-            let priority =
-                get_item_priority(common_item).expect("common item should have priority") as i32;
-            priority as i64
-        })
-        .sum::<i64>()
-        .into()
-}
+impl Solution for Day3 {
+    /// The items of every rucksack.
+    type Parsed = Vec<String>;
 
-pub fn part2(input: &str) -> PuzzleResult {
-    let groups = transform_input_for_part2(input);
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        transform_input(input)
+    }
 
-    groups
-        .iter()
-        .map(|groups| {
-            let common_item =
-                get_common_item(groups).expect("rucksack groups should have a common item");
-            let priority =
-                get_item_priority(common_item).expect("common item should have priority") as i32;
-            priority as i64
-        })
-        .sum::<i64>()
-        .into()
+    fn part1(rucksacks: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        let priorities = split_compartments(rucksacks)
+            .iter()
+            .map(|compartments| {
+                let common_item = get_common_item(compartments).ok_or_else(|| {
+                    SolveError::invalid_input("rucksack compartments should have a common item")
+                })?;
+                let priority = get_item_priority(common_item).ok_or_else(|| {
+                    SolveError::invalid_input(format!("item '{common_item}' should have priority"))
+                })? as i32;
+                Ok(priority as i64)
+            })
+            .collect::<Result<Vec<_>, SolveError>>()?;
+
+        Ok(priorities.iter().sum::<i64>().into())
+    }
+
+    fn part2(rucksacks: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        if rucksacks.len() % 3 != 0 {
+            return Err(SolveError::invalid_input(
+                "number of rucksacks should be a multiple of 3",
+            ));
+        }
+
+        let priorities = rucksacks
+            .chunks(3)
+            .map(|groups| {
+                let common_item = get_common_item(groups).ok_or_else(|| {
+                    SolveError::invalid_input("rucksack groups should have a common item")
+                })?;
+                let priority = get_item_priority(common_item).ok_or_else(|| {
+                    SolveError::invalid_input(format!("item '{common_item}' should have priority"))
+                })? as i32;
+                Ok(priority as i64)
+            })
+            .collect::<Result<Vec<_>, SolveError>>()?;
+
+        Ok(priorities.iter().sum::<i64>().into())
+    }
+
+    /// `size` groups of three rucksacks. Compartments share a single item, and so do the rucksacks
+    /// of a group.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut items = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+        let mut rucksacks = String::new();
+
+        for _ in 0..size.max(1) {
+            rng.shuffle(&mut items);
+            let (&badge, other_items) = items.split_first().expect("there should be items");
+
+            // each rucksack of the group takes its other items from its own third of the items
+            for own_items in other_items.chunks(other_items.len() / 3) {
+                rucksacks.push_str(&generate_rucksack(rng, badge, own_items));
+                rucksacks.push('\n');
+            }
+        }
+
+        Some(rucksacks)
+    }
 }
 
 fn get_common_item(item_groups: &[String]) -> Option<char> {
@@ -39,9 +82,7 @@ fn get_common_item(item_groups: &[String]) -> Option<char> {
         .iter()
         .map(|group| group.chars().collect::<HashSet<_>>());
 
-    let mut common_items = unique_groups_items
-        .next()
-        .expect("there should be at least one group");
+    let mut common_items = unique_groups_items.next()?;
 
     for group_items in unique_groups_items {
         common_items = common_items.intersection(&group_items).copied().collect();
@@ -70,34 +111,58 @@ fn get_item_priority(item: char) -> Option<u8> {
     Some(priority)
 }
 
-fn transform_input_for_part1(input: &str) -> Vec<[String; 2]> {
+fn transform_input(input: &str) -> Result<Vec<String>, SolveError> {
     input
         .lines()
         .map(|rucksack_items| {
-            let number_of_items = rucksack_items.len();
-            let number_of_items_in_compartment = number_of_items / 2;
-            let first_compartment_items =
-                rucksack_items[..number_of_items_in_compartment].to_owned();
-            let second_compartment_items =
-                rucksack_items[number_of_items_in_compartment..].to_owned();
-            [first_compartment_items, second_compartment_items]
+            if !rucksack_items.is_ascii() {
+                return Err(SolveError::invalid_input(format!(
+                    "rucksack items should be ASCII letters, got '{rucksack_items}'"
+                )));
+            }
+
+            Ok(rucksack_items.to_owned())
         })
         .collect()
 }
 
-fn transform_input_for_part2(input: &str) -> Vec<[String; 3]> {
-    input
-        .lines()
-        .collect::<Vec<&str>>()
-        .chunks(3)
-        .map(|group| {
+fn split_compartments(rucksacks: &[String]) -> Vec<[String; 2]> {
+    rucksacks
+        .iter()
+        .map(|rucksack_items| {
+            let number_of_items_in_compartment = rucksack_items.len() / 2;
+            let (first_compartment_items, second_compartment_items) =
+                rucksack_items.split_at(number_of_items_in_compartment);
             [
-                group[0].to_owned(),
-                group[1].to_owned(),
-                group[2].to_owned(),
+                first_compartment_items.to_owned(),
+                second_compartment_items.to_owned(),
             ]
         })
         .collect()
 }
 
-test_solvers!(157, 70);
+/// A rucksack holding `badge` and items among `own_items`, whose compartments only share one item.
+fn generate_rucksack(rng: &mut Rng, badge: char, own_items: &[char]) -> String {
+    let (first_items, second_items) = own_items.split_at(own_items.len() / 2);
+    let shared_item = if rng.ratio(1, 10) {
+        badge
+    } else {
+        *rng.choose(own_items)
+    };
+    let compartment_size = rng.range(2..=16);
+
+    let mut first_compartment = vec![shared_item, badge];
+    let mut second_compartment = vec![shared_item];
+    while first_compartment.len() < compartment_size {
+        first_compartment.push(*rng.choose(first_items));
+    }
+    while second_compartment.len() < compartment_size {
+        second_compartment.push(*rng.choose(second_items));
+    }
+    rng.shuffle(&mut first_compartment);
+    rng.shuffle(&mut second_compartment);
+
+    first_compartment.into_iter().chain(second_compartment).collect()
+}
+
+test_solvers!(Day3);
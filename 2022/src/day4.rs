@@ -1,46 +1,76 @@
 use std::str::FromStr;
 
-use crate::{shared::PuzzleResult, test_solvers};
+use aoc_framework::{
+    generate::Rng,
+    diagnostic::Diagnostic,
+    shared::{PuzzleResult, ReferenceSolver, Solution, SolveError},
+    test_solvers,
+};
 
-mod cleaning;
+pub mod cleaning;
+pub mod reference;
 
-pub fn part1(input: &str) -> PuzzleResult {
-    let pairs = transform_input(input);
+pub struct Day4;
 
-    let result = pairs
-        .iter()
-        .filter(|(first, second)| first.contains(second) || second.contains(first))
-        .collect::<Vec<_>>()
-        .len() as i64;
+impl Solution for Day4 {
+    type Parsed = Vec<(cleaning::Section, cleaning::Section)>;
 
-    result.into()
-}
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        transform_input(input)
+    }
+
+    fn part1(pairs: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        let result = pairs
+            .iter()
+            .filter(|(first, second)| first.contains(second) || second.contains(first))
+            .collect::<Vec<_>>()
+            .len();
+
+        Ok(result.into())
+    }
+
+    fn part2(pairs: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        let result = pairs
+            .iter()
+            .filter(|(first, second)| first.overlap(second))
+            .collect::<Vec<_>>()
+            .len();
+
+        Ok(result.into())
+    }
 
-pub fn part2(input: &str) -> PuzzleResult {
-    let pairs = transform_input(input);
+    const REFERENCES: [Option<ReferenceSolver>; 2] =
+        [Some(reference::part1), Some(reference::part2)];
 
-    let result = pairs
-        .iter()
-        .filter(|(first, second)| first.overlap(second))
-        .collect::<Vec<_>>()
-        .len() as i64;
+    /// `size` pairs of sections between 1 and 99.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut section = || {
+            let start = rng.range(1..=99);
+            format!("{start}-{}", rng.range(start..=99))
+        };
 
-    result.into()
+        Some(
+            (0..size.max(1))
+                .map(|_| format!("{},{}\n", section(), section()))
+                .collect(),
+        )
+    }
 }
 
-fn transform_input(input: &str) -> Vec<(cleaning::Section, cleaning::Section)> {
+fn transform_input(input: &str) -> Result<Vec<(cleaning::Section, cleaning::Section)>, SolveError> {
     input
         .lines()
         .map(|pair| {
-            let assignments: Vec<_> = pair.split(",").collect();
-            let first = assignments[0];
-            let second = assignments[1];
-            (
-                cleaning::Section::from_str(first).expect("first assignment should be valid"),
-                cleaning::Section::from_str(second).expect("second assignment should be valid"),
-            )
+            let (first, second) = pair.split_once(',').ok_or_else(|| {
+                Diagnostic::new("expected two assignments separated by ','", input, pair)
+            })?;
+            Ok((
+                cleaning::Section::from_str(first).map_err(|err| err.relative_to(input, first))?,
+                cleaning::Section::from_str(second)
+                    .map_err(|err| err.relative_to(input, second))?,
+            ))
         })
         .collect()
 }
 
-test_solvers!(2, 4);
+test_solvers!(Day4);
@@ -1,4 +1,6 @@
-use std::str::FromStr;
+use std::{error::Error, fmt::Display, num::ParseIntError, str::FromStr};
+
+use aoc_framework::diagnostic::Diagnostic;
 
 #[derive(Debug, Clone)]
 pub struct Section {
@@ -20,25 +22,39 @@ impl Section {
 }
 
 #[derive(Debug, Clone)]
-pub struct ParseSectionError;
+pub enum ParseSectionError {
+    MissingSeparatorError,
+    InvalidBoundError(ParseIntError),
+}
+
+impl Display for ParseSectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingSeparatorError => write!(f, "expected a '<start>-<end>' section"),
+            Self::InvalidBoundError(err) => write!(f, "invalid section bound: {err}"),
+        }
+    }
+}
+
+impl Error for ParseSectionError {}
 
 impl FromStr for Section {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<_> = s.split("-").map(|part| part.parse::<u32>()).collect();
-        if parts.len() != 2 {
-            return Err(ParseSectionError);
-        }
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| Diagnostic::new(ParseSectionError::MissingSeparatorError, s, s))?;
 
-        let start = &parts[0];
-        let end = &parts[1];
-        match (start, end) {
-            (Ok(start), Ok(end)) => Ok(Self {
-                start: *start,
-                end: *end,
-            }),
-            _ => Err(ParseSectionError),
-        }
+        let parse_bound = |bound: &str| {
+            bound
+                .parse::<u32>()
+                .map_err(|err| Diagnostic::new(ParseSectionError::InvalidBoundError(err), s, bound))
+        };
+
+        Ok(Self {
+            start: parse_bound(start)?,
+            end: parse_bound(end)?,
+        })
     }
 
-    type Err = ParseSectionError;
+    type Err = Diagnostic<ParseSectionError>;
 }
@@ -0,0 +1,50 @@
+//! Reference solutions checking the pairs on the sets of their sections rather than on their
+//! bounds.
+
+use std::collections::HashSet;
+
+use aoc_framework::shared::{PuzzleResult, SolveError};
+
+pub fn part1(input: &str) -> Result<PuzzleResult, SolveError> {
+    let pairs = section_sets(input)?;
+
+    Ok(pairs
+        .iter()
+        .filter(|(first, second)| first.is_subset(second) || second.is_subset(first))
+        .count()
+        .into())
+}
+
+pub fn part2(input: &str) -> Result<PuzzleResult, SolveError> {
+    let pairs = section_sets(input)?;
+
+    Ok(pairs
+        .iter()
+        .filter(|(first, second)| !first.is_disjoint(second))
+        .count()
+        .into())
+}
+
+/// Sections of both elves of each pair.
+type Pair = (HashSet<u32>, HashSet<u32>);
+
+fn section_sets(input: &str) -> Result<Vec<Pair>, SolveError> {
+    let sections = |assignment: &str| -> Result<HashSet<u32>, SolveError> {
+        let (start, end) = assignment
+            .split_once('-')
+            .ok_or_else(|| SolveError::invalid_input("expected a '<start>-<end>' section"))?;
+
+        Ok((start.parse()?..=end.parse()?).collect())
+    };
+
+    input
+        .lines()
+        .map(|pair| {
+            let (first, second) = pair
+                .split_once(',')
+                .ok_or_else(|| SolveError::invalid_input("expected two assignments"))?;
+
+            Ok((sections(first)?, sections(second)?))
+        })
+        .collect()
+}
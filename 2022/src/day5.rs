@@ -1,58 +1,146 @@
 use std::str::FromStr;
 
-use crate::{shared::PuzzleResult, test_solvers};
+use aoc_framework::{
+    generate::Rng,
+    diagnostic::Diagnostic,
+    shared::{PuzzleResult, Solution, SolveError},
+    test_solvers,
+};
 
-mod crane;
+pub mod crane;
 
 use crane::{Crane, CrateMover, MoveInstruction};
 
-pub fn part1(input: &str) -> PuzzleResult {
-    let (mut crane, move_instructions) = transform_input(input);
+pub struct Day5;
 
-    for move_instruction in move_instructions {
-        crane.move_containers(&move_instruction, CrateMover::V9000);
+impl Solution for Day5 {
+    type Parsed = (Crane, Vec<MoveInstruction>);
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        transform_input(input)
     }
 
-    get_result(&crane)
-}
+    fn part1(parsed: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        let (crane, move_instructions) = parsed;
+        let mut crane = crane.clone();
+
+        for move_instruction in move_instructions {
+            crane.move_containers(move_instruction, CrateMover::V9000)?;
+        }
+
+        get_result(&crane)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        let (crane, move_instructions) = parsed;
+        let mut crane = crane.clone();
 
-pub fn part2(input: &str) -> PuzzleResult {
-    let (mut crane, move_instructions) = transform_input(input);
+        for move_instruction in move_instructions {
+            crane.move_containers(move_instruction, CrateMover::V9001)?;
+        }
 
-    for move_instruction in move_instructions {
-        crane.move_containers(&move_instruction, CrateMover::V9001);
+        get_result(&crane)
     }
 
-    get_result(&crane)
+    /// `size` moves between 3 to 9 stacks. Moves never empty a stack, so that every stack has a
+    /// crate on top in the end.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let labels = ('A'..='Z').collect::<Vec<_>>();
+        let stacks = (0..rng.range(3..=9))
+            .map(|_| {
+                (0..rng.range(2..=8))
+                    .map(|_| *rng.choose(&labels))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut input = String::new();
+        let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+        for level in (0..height).rev() {
+            let row = stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(label) => format!("[{label}]"),
+                    None => "   ".to_owned(),
+                })
+                .collect::<Vec<_>>();
+            input.push_str(&row.join(" "));
+            input.push('\n');
+        }
+        let numbers = (1..=stacks.len())
+            .map(|number| format!(" {number} "))
+            .collect::<Vec<_>>();
+        input.push_str(&numbers.join(" "));
+        input.push_str("\n\n");
+
+        let mut heights = stacks.iter().map(Vec::len).collect::<Vec<_>>();
+        for _ in 0..size.max(1) {
+            let movable = (0..heights.len())
+                .filter(|&stack| heights[stack] > 1)
+                .collect::<Vec<_>>();
+            let from = *rng.choose(&movable);
+            let to = (from + rng.range(1..=heights.len() - 1)) % heights.len();
+            let quantity = rng.range(1..=heights[from] - 1);
+
+            heights[from] -= quantity;
+            heights[to] += quantity;
+            input.push_str(&format!("move {quantity} from {} to {}\n", from + 1, to + 1));
+        }
+
+        Some(input)
+    }
 }
 
-fn transform_input(input: &str) -> (Crane, Vec<MoveInstruction>) {
-    let input_parts: Vec<_> = input.split("\n\n").collect();
-    let start_position = input_parts[0];
-    let move_instructions = input_parts[1];
+fn transform_input(input: &str) -> Result<(Crane, Vec<MoveInstruction>), SolveError> {
+    let (start_position, move_instructions) = input.split_once("\n\n").ok_or_else(|| {
+        SolveError::invalid_input(
+            "start position and move instructions should be separated by an empty line",
+        )
+    })?;
 
-    let crane = Crane::from_str(start_position).expect("start position should be valid");
+    let crane =
+        Crane::from_str(start_position).map_err(|err| err.relative_to(input, start_position))?;
 
-    let move_instructions: Vec<_> = move_instructions
+    let move_instructions = move_instructions
         .lines()
+        .filter(|instruction| !instruction.is_empty())
         .map(|instruction| {
-            instruction
+            let numbers = instruction
                 .split(' ')
                 .filter_map(|part| part.parse::<usize>().ok())
-                .collect::<MoveInstruction>()
+                .collect::<Vec<_>>();
+
+            if numbers.len() != 3 {
+                return Err(Diagnostic::new(
+                    "expected a 'move <n> from <n> to <n>' instruction",
+                    input,
+                    instruction,
+                ));
+            }
+
+            Ok(numbers.into_iter().collect::<MoveInstruction>())
         })
-        .collect();
+        .collect::<Result<Vec<_>, _>>()?;
 
-    (crane, move_instructions)
+    Ok((crane, move_instructions))
 }
 
-fn get_result(crane: &Crane) -> PuzzleResult {
-    crane
+fn get_result(crane: &Crane) -> Result<PuzzleResult, SolveError> {
+    let top_containers = crane
         .top_containers()
-        .map(|container| *container.expect("all top containers should exist"))
+        .enumerate()
+        .map(|(stack_index, container)| {
+            container.copied().ok_or_else(|| {
+                SolveError::invalid_input(format!("stack {} should not be empty", stack_index + 1))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(top_containers
+        .iter()
         .map(|container| container.label())
         .collect::<String>()
-        .into()
+        .into())
 }
 
-test_solvers!("CMZ".to_string(), "MCD".to_string());
+test_solvers!(Day5);
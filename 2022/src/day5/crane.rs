@@ -1,4 +1,6 @@
-use std::str::FromStr;
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use aoc_framework::{diagnostic::Diagnostic, shared::SolveError};
 
 #[derive(Debug, Clone, Copy)]
 pub struct Container {
@@ -49,37 +51,93 @@ impl CrateMover {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Crane {
     containers_stacks: Vec<Vec<Container>>,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub enum MoveContainersError {
+    MissingStackError(usize),
+    NotEnoughContainersError {
+        stack: usize,
+        quantity: usize,
+        available: usize,
+    },
+}
+
+impl Display for MoveContainersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingStackError(stack) => write!(f, "stack {stack} does not exist"),
+            Self::NotEnoughContainersError {
+                stack,
+                quantity,
+                available,
+            } => write!(
+                f,
+                "cannot move {quantity} containers from stack {stack} holding {available}"
+            ),
+        }
+    }
+}
+
+impl Error for MoveContainersError {}
+
+impl From<MoveContainersError> for SolveError {
+    fn from(err: MoveContainersError) -> Self {
+        Self::InvalidInputError(err.to_string())
+    }
+}
+
 impl Crane {
-    fn pick_containers_up<'a>(&mut self, move_instruction: &MoveInstruction) -> Vec<Container> {
-        let from_index = move_instruction.from - 1;
+    fn stack_index(&self, stack: usize) -> Result<usize, MoveContainersError> {
+        match stack.checked_sub(1) {
+            Some(index) if index < self.containers_stacks.len() => Ok(index),
+            _ => Err(MoveContainersError::MissingStackError(stack)),
+        }
+    }
+
+    fn pick_containers_up(
+        &mut self,
+        move_instruction: &MoveInstruction,
+    ) -> Result<Vec<Container>, MoveContainersError> {
+        let from_index = self.stack_index(move_instruction.from)?;
         let from_len = self.containers_stacks[from_index].len();
 
-        self.containers_stacks[from_index].split_off(from_len - move_instruction.quantity)
+        let split_index = from_len.checked_sub(move_instruction.quantity).ok_or(
+            MoveContainersError::NotEnoughContainersError {
+                stack: move_instruction.from,
+                quantity: move_instruction.quantity,
+                available: from_len,
+            },
+        )?;
+
+        Ok(self.containers_stacks[from_index].split_off(split_index))
     }
 
     fn put_containers_down(
         &mut self,
         move_instruction: &MoveInstruction,
         containers_iter: impl IntoIterator<Item = Container>,
-    ) -> () {
-        let to_index = move_instruction.to - 1;
+    ) -> Result<(), MoveContainersError> {
+        let to_index = self.stack_index(move_instruction.to)?;
         self.containers_stacks[to_index].extend(containers_iter);
+
+        Ok(())
     }
 
     pub fn move_containers(
         &mut self,
         move_instruction: &MoveInstruction,
         crate_mover: CrateMover,
-    ) -> () {
-        let containers_to_move = self.pick_containers_up(move_instruction);
+    ) -> Result<(), MoveContainersError> {
+        // check the destination up front so a failed move leaves the stacks untouched
+        self.stack_index(move_instruction.to)?;
+        let containers_to_move = self.pick_containers_up(move_instruction)?;
 
         let ordered_containers = crate_mover.order_containers(containers_to_move);
-        self.put_containers_down(move_instruction, ordered_containers);
+        self.put_containers_down(move_instruction, ordered_containers)
     }
 
     pub fn top_containers(&self) -> impl Iterator<Item = Option<&Container>> {
@@ -94,17 +152,38 @@ pub enum ParseCraneError {
     UnexpectedTokenError(char),
 }
 
+impl Display for ParseCraneError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ExpectedSeparatorError(token) => {
+                write!(
+                    f,
+                    "expected ' ' or a new line between stacks, got '{token}'"
+                )
+            }
+            Self::ExpectedNextTokenToExistError => write!(f, "unexpected end of stacks drawing"),
+            Self::UnexpectedTokenError(token) => {
+                write!(f, "unexpected '{token}' in stacks drawing")
+            }
+        }
+    }
+}
+
+impl Error for ParseCraneError {}
+
 impl FromStr for Crane {
-    type Err = ParseCraneError;
+    type Err = Diagnostic<ParseCraneError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut tokens = s.chars().peekable();
+        let mut tokens = s.char_indices().peekable();
+        let end_of_drawing =
+            || Diagnostic::at_char(ParseCraneError::ExpectedNextTokenToExistError, s, s.len());
 
         let mut stack_index = 0usize;
         let mut expect_separator = false;
         let mut containers_stacks = Vec::<Vec<Container>>::new();
 
-        while let Some(token) = tokens.next() {
+        while let Some((offset, token)) = tokens.next() {
             if containers_stacks.get(stack_index).is_none() {
                 containers_stacks.push(Vec::new());
             }
@@ -114,27 +193,25 @@ impl FromStr for Crane {
                 } else if token == '\n' {
                     stack_index = 0;
                 } else {
-                    return Err(ParseCraneError::ExpectedSeparatorError(token));
+                    return Err(Diagnostic::at_char(
+                        ParseCraneError::ExpectedSeparatorError(token),
+                        s,
+                        offset,
+                    ));
                 }
                 expect_separator = false;
                 continue;
             }
             if token == '[' {
-                let label = tokens
-                    .peek()
-                    .ok_or(ParseCraneError::ExpectedNextTokenToExistError)?;
-                let container = Container::from(*label);
+                let &(_, label) = tokens.peek().ok_or_else(end_of_drawing)?;
+                let container = Container::from(label);
                 containers_stacks[stack_index].insert(0, container);
 
                 tokens.next();
                 tokens.next();
                 expect_separator = true;
             } else if token == ' ' {
-                if tokens
-                    .peek()
-                    .ok_or(ParseCraneError::ExpectedNextTokenToExistError)?
-                    .is_numeric()
-                {
+                if tokens.peek().ok_or_else(end_of_drawing)?.1.is_numeric() {
                     break;
                 }
 
@@ -142,7 +219,11 @@ impl FromStr for Crane {
                 tokens.next();
                 expect_separator = true;
             } else {
-                return Err(ParseCraneError::UnexpectedTokenError(token));
+                return Err(Diagnostic::at_char(
+                    ParseCraneError::UnexpectedTokenError(token),
+                    s,
+                    offset,
+                ));
             }
         }
 
@@ -1,27 +1,66 @@
 use std::collections::HashSet;
 
-use crate::{shared::PuzzleResult, test_solvers};
+use aoc_framework::{
+    generate::Rng,
+    shared::{PuzzleResult, Solution, SolveError},
+    test_solvers,
+};
 
-pub fn part1(input: &str) -> PuzzleResult {
-    chars_precessed_to_unique_sequence(input, 4)
-}
+pub struct Day6;
 
-pub fn part2(input: &str) -> PuzzleResult {
-    chars_precessed_to_unique_sequence(input, 14)
-}
+impl Solution for Day6 {
+    type Parsed = Vec<char>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        Ok(input.chars().collect())
+    }
+
+    fn part1(chars: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        chars_precessed_to_unique_sequence(chars, 4)
+    }
+
+    fn part2(chars: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        chars_precessed_to_unique_sequence(chars, 14)
+    }
 
-fn chars_precessed_to_unique_sequence(input: &str, sequence_size: usize) -> PuzzleResult {
-    let chars = input.chars().collect::<Vec<_>>();
+    /// A datastream of `size` characters, at least 14, with a start-of-message marker at a random
+    /// position.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut letters = ('a'..='z').collect::<Vec<_>>();
+        rng.shuffle(&mut letters);
+
+        let size = size.max(14);
+        let marker_start = rng.range(0..=size - 14);
+
+        // 13 different letters are too few for a marker
+        let mut datastream = (0..marker_start)
+            .map(|_| *rng.choose(&letters[..13]))
+            .collect::<String>();
+        let mut marker = letters.clone();
+        rng.shuffle(&mut marker);
+        datastream.extend(&marker[..14]);
+        datastream.extend((marker_start + 14..size).map(|_| *rng.choose(&letters)));
+
+        Some(datastream)
+    }
+}
 
-    let (index, _) =
-        find_unique_sequence(&chars, sequence_size).expect("unique sequence should exist");
+fn chars_precessed_to_unique_sequence(
+    chars: &[char],
+    sequence_size: usize,
+) -> Result<PuzzleResult, SolveError> {
+    let (index, _) = find_unique_sequence(chars, sequence_size).ok_or_else(|| {
+        SolveError::invalid_input(format!(
+            "datastream should contain {sequence_size} different characters in a row"
+        ))
+    })?;
 
-    let chars_processed = (index + sequence_size) as i64;
+    let chars_processed = index + sequence_size;
 
-    chars_processed.into()
+    Ok(chars_processed.into())
 }
 
-fn find_unique_sequence(chars: &Vec<char>, sequence_size: usize) -> Option<(usize, &[char])> {
+fn find_unique_sequence(chars: &[char], sequence_size: usize) -> Option<(usize, &[char])> {
     chars
         .windows(sequence_size)
         .enumerate()
@@ -31,4 +70,4 @@ fn find_unique_sequence(chars: &Vec<char>, sequence_size: usize) -> Option<(usiz
         })
 }
 
-test_solvers!(7, 19);
+test_solvers!(Day6);
@@ -1,56 +1,149 @@
 use std::{collections::HashMap, iter::Peekable, str::FromStr, vec};
 
-use crate::{shared::PuzzleResult, test_solvers};
+use aoc_framework::{
+    generate::Rng,
+    diagnostic::Diagnostic,
+    shared::{PuzzleResult, Solution, SolveError},
+    test_solvers,
+};
 
 use self::fs::{Directory, File, Node};
 
-mod fs;
+pub mod fs;
 
-pub fn part1(input: &str) -> PuzzleResult {
-    let mut commands = transform_input(input).peekable();
+pub struct Day7;
 
-    let dir_sizes = dir_sizes(&mut commands);
+impl Solution for Day7 {
+    type Parsed = HashMap<String, u64>;
 
-    let result = dir_sizes
-        .values()
-        .filter(|&&dir_size| dir_size <= 100_000)
-        .sum::<u32>() as i64;
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        let mut commands = transform_input(input)?.peekable();
 
-    result.into()
-}
+        dir_sizes(input, &mut commands)
+    }
+
+    fn part1(dir_sizes: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        let result = dir_sizes
+            .values()
+            .filter(|&&dir_size| dir_size <= 100_000)
+            .sum::<u64>();
 
-pub fn part2(input: &str) -> PuzzleResult {
-    let mut commands = transform_input(input).peekable();
+        Ok(result.into())
+    }
+
+    fn part2(dir_sizes: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        const DISK_SPACE: u64 = 70_000_000;
+        const REQUIRED_SPACE: u64 = 30_000_000;
 
-    let dir_sizes = dir_sizes(&mut commands);
+        let disk_usage = dir_sizes["/"];
 
-    const DISK_SPACE: u32 = 70_000_000;
-    const REQUIRED_SPACE: u32 = 30_000_000;
+        let space_left = DISK_SPACE.checked_sub(disk_usage).ok_or_else(|| {
+            SolveError::invalid_input(format!("disk usage {disk_usage} exceeds the disk space"))
+        })?;
+        let space_needed = REQUIRED_SPACE.saturating_sub(space_left);
 
-    let disk_usage = dir_sizes["/"];
+        let result = dir_sizes
+            .values()
+            .copied()
+            .filter(|&dir_size| dir_size >= space_needed)
+            .min()
+            .expect("root directory should be large enough to free the space needed");
 
-    let space_left = DISK_SPACE - disk_usage;
-    let space_needed = REQUIRED_SPACE - space_left;
+        Ok(result.into())
+    }
 
-    let result = dir_sizes
-        .values()
-        .copied()
-        .filter(|&dir_size| dir_size >= space_needed)
-        .min()
-        .unwrap() as i64;
+    /// A terminal session exploring `size` directories, each listed once. Half of the directories
+    /// are created in the previous one, making deep trees. File sizes add up to a disk usage
+    /// between 41M and 69M, so that some space has to be freed.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut children = vec![vec![]; size.max(1)];
+        let mut names = vec!["/".to_owned()];
+        for dir in 1..children.len() {
+            let parent = if rng.ratio(1, 2) {
+                dir - 1
+            } else {
+                rng.range(0..=dir - 1)
+            };
+
+            let name = loop {
+                let name = generate_name(rng);
+                if children[parent]
+                    .iter()
+                    .all(|&sibling: &usize| names[sibling] != name)
+                {
+                    break name;
+                }
+            };
+
+            children[parent].push(dir);
+            names.push(name);
+        }
+
+        // files share a disk usage leaving less than the space required by the update
+        let disk_usage = rng.range(41_000_000..=69_000_000);
+        let weights = children
+            .iter()
+            .map(|_| {
+                (0..rng.range(0..=4))
+                    .map(|_| rng.range(1..=1000))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let total_weight = weights.iter().flatten().sum::<usize>().max(1);
+
+        let mut session = "$ cd /\n".to_owned();
+        let mut steps = vec![Some(0)];
+        // `None` goes back to the parent directory
+        while let Some(step) = steps.pop() {
+            let Some(dir) = step else {
+                session.push_str("$ cd ..\n");
+                continue;
+            };
+
+            if dir != 0 {
+                session.push_str(&format!("$ cd {}\n", names[dir]));
+            }
+            session.push_str("$ ls\n");
+
+            let mut listing = children[dir]
+                .iter()
+                .map(|&child| format!("dir {}\n", names[child]))
+                .chain(weights[dir].iter().map(|weight| {
+                    let name = generate_name(rng);
+                    let extension = if rng.ratio(1, 2) {
+                        format!(".{}", generate_name(rng))
+                    } else {
+                        String::new()
+                    };
+                    let file_size = (disk_usage * weight / total_weight).max(1);
+                    format!("{file_size} {name}{extension}\n")
+                }))
+                .collect::<Vec<_>>();
+            rng.shuffle(&mut listing);
+            session.extend(listing);
+
+            for &child in children[dir].iter().rev() {
+                steps.push(None);
+                steps.push(Some(child));
+            }
+        }
 
-    result.into()
+        Some(session)
+    }
 }
 
-fn dir_sizes<'a, I: Iterator<Item = &'a str>>(commands: &mut Peekable<I>) -> HashMap<String, u32> {
+fn dir_sizes<'a, I: Iterator<Item = &'a str>>(
+    input: &str,
+    commands: &mut Peekable<I>,
+) -> Result<HashMap<String, u64>, SolveError> {
     let mut path = vec!["/".to_owned()];
     let mut dir_sizes = HashMap::new();
-    dir_sizes.insert("/".to_owned(), 0u32);
+    dir_sizes.insert("/".to_owned(), 0u64);
 
     while let Some(cmd) = commands.next() {
         match cmd.split(' ').collect::<Vec<_>>().as_slice() {
             ["$", "ls"] => {
-                let nodes = ls_result(commands);
+                let nodes = ls_result(input, commands)?;
                 let dirs_paths = dirs_paths(&dir_sizes);
                 let current_path = path.join("");
 
@@ -63,55 +156,58 @@ fn dir_sizes<'a, I: Iterator<Item = &'a str>>(commands: &mut Peekable<I>) -> Has
                 path.push(trailing_slash(cd_path));
             }
 
-            _ => panic!("unexpected command"),
+            _ => return Err(Diagnostic::new("unexpected command", input, cmd).into()),
         }
     }
 
-    dir_sizes
+    Ok(dir_sizes)
 }
 
-fn ls_result<'a, I: Iterator<Item = &'a str>>(commands: &mut Peekable<I>) -> Vec<Node> {
+fn ls_result<'a, I: Iterator<Item = &'a str>>(
+    input: &str,
+    commands: &mut Peekable<I>,
+) -> Result<Vec<Node>, SolveError> {
     let mut result = vec![];
 
     while is_next_result(commands) {
         let node_info = commands.next().unwrap();
-        let node = Node::from_str(node_info).expect("node should be valid");
+        let node = Node::from_str(node_info).map_err(|err| err.relative_to(input, node_info))?;
 
         result.push(node);
     }
 
-    result
+    Ok(result)
 }
 
 fn update_dirs(
-    mut dir_sizes: &mut HashMap<String, u32>,
+    dir_sizes: &mut HashMap<String, u64>,
     dirs_paths: &Vec<String>,
     path: &str,
     nodes: &Vec<Node>,
-) -> () {
+) {
     for node in nodes {
         match node {
             Node::DirectoryNode(dir) => {
-                create_dir(&mut dir_sizes, path, dir);
+                create_dir(dir_sizes, path, dir);
             }
             Node::FileNode(file) => {
-                add_file(&mut dir_sizes, &dirs_paths, path, file);
+                add_file(dir_sizes, dirs_paths, path, file);
             }
         };
     }
 }
 
-fn create_dir(dir_sizes: &mut HashMap<String, u32>, path: &str, dir: &Directory) -> () {
+fn create_dir(dir_sizes: &mut HashMap<String, u64>, path: &str, dir: &Directory) {
     let dir_path = trailing_slash(&(path.to_owned() + dir.name()));
     dir_sizes.insert(dir_path, 0);
 }
 
 fn add_file(
-    dir_sizes: &mut HashMap<String, u32>,
+    dir_sizes: &mut HashMap<String, u64>,
     dirs_paths: &Vec<String>,
     path: &str,
     file: &File,
-) -> () {
+) {
     for dir_path in dirs_paths {
         // update sizes of all parent directories
         if path.starts_with(dir_path) {
@@ -121,7 +217,7 @@ fn add_file(
     }
 }
 
-fn dirs_paths(dir_sizes: &HashMap<String, u32>) -> Vec<String> {
+fn dirs_paths(dir_sizes: &HashMap<String, u64>) -> Vec<String> {
     dir_sizes.keys().cloned().collect()
 }
 
@@ -141,18 +237,41 @@ fn trailing_slash(path: &str) -> String {
     path.to_owned() + "/"
 }
 
-fn transform_input(input: &str) -> impl Iterator<Item = &str> {
+fn transform_input(input: &str) -> Result<impl Iterator<Item = &str>, SolveError> {
     let mut iter = input.lines().peekable();
 
-    if iter
-        .peek()
-        .map(|&first_cmd| first_cmd != "$ cd /")
-        .unwrap_or(true)
-    {
-        panic!("first command should be '$ cd /'")
+    let first_cmd = iter.peek().copied().unwrap_or(input);
+    if first_cmd != "$ cd /" {
+        return Err(Diagnostic::new("first command should be '$ cd /'", input, first_cmd).into());
     }
 
-    iter.skip(1)
+    Ok(iter.skip(1))
+}
+
+fn generate_name(rng: &mut Rng) -> String {
+    (0..rng.range(1..=8))
+        .map(|_| (b'a' + rng.range(0..=25) as u8) as char)
+        .collect()
+}
+
+#[cfg(test)]
+mod size_tests {
+    use aoc_framework::shared::{PuzzleResult, Solution};
+
+    use super::Day7;
+
+    #[test]
+    fn sizes_add_up_past_4_gib() {
+        let input = "$ cd /\n$ ls\ndir d\n3000000000 a\n3000000000 b\n$ cd d\n$ ls\n50000 c\n";
+
+        let dir_sizes = Day7::parse(input).unwrap();
+
+        assert_eq!(dir_sizes["/"], 6_000_050_000);
+        assert!(matches!(
+            Day7::part1(&dir_sizes),
+            Ok(PuzzleResult::Unsigned(50_000))
+        ));
+    }
 }
 
-test_solvers!(95437, 24933642);
+test_solvers!(Day7);
@@ -1,16 +1,18 @@
-use std::{num::ParseIntError, str::FromStr};
+use std::{error::Error, fmt::Display, num::ParseIntError, str::FromStr};
+
+use aoc_framework::diagnostic::Diagnostic;
 
 #[derive(Debug, Clone)]
 pub struct File {
-    size: u32,
+    size: u64,
 }
 
 impl File {
-    pub fn new(size: u32) -> Self {
+    pub fn new(size: u64) -> Self {
         Self { size }
     }
 
-    pub fn size(&self) -> u32 {
+    pub fn size(&self) -> u64 {
         self.size
     }
 }
@@ -21,13 +23,28 @@ pub enum ParseFileErr {
     InvalidSizeErr(ParseIntError),
 }
 
+impl Display for ParseFileErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingDelimiterErr => write!(f, "expected a '<size> <name>' file entry"),
+            Self::InvalidSizeErr(err) => write!(f, "invalid file size: {err}"),
+        }
+    }
+}
+
+impl Error for ParseFileErr {}
+
 impl FromStr for File {
-    type Err = ParseFileErr;
+    type Err = Diagnostic<ParseFileErr>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (size, _) = s.split_once(" ").ok_or(ParseFileErr::MissingDelimiterErr)?;
+        let (size, _) = s
+            .split_once(" ")
+            .ok_or_else(|| Diagnostic::new(ParseFileErr::MissingDelimiterErr, s, s))?;
 
-        let size = size.parse::<u32>().map_err(ParseFileErr::InvalidSizeErr)?;
+        let size = size
+            .parse::<u64>()
+            .map_err(|err| Diagnostic::new(ParseFileErr::InvalidSizeErr(err), s, size))?;
 
         Ok(Self::new(size))
     }
@@ -53,13 +70,23 @@ pub enum ParseDirectoryErr {
     MissingDirPrefixErr,
 }
 
+impl Display for ParseDirectoryErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingDirPrefixErr => write!(f, "expected a 'dir <name>' entry"),
+        }
+    }
+}
+
+impl Error for ParseDirectoryErr {}
+
 impl FromStr for Directory {
-    type Err = ParseDirectoryErr;
+    type Err = Diagnostic<ParseDirectoryErr>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         s.strip_prefix("dir ")
             .map(|dir_name| Directory::new(dir_name.to_owned()))
-            .ok_or(ParseDirectoryErr::MissingDirPrefixErr)
+            .ok_or_else(|| Diagnostic::new(ParseDirectoryErr::MissingDirPrefixErr, s, s))
     }
 }
 
@@ -70,7 +97,7 @@ pub enum Node {
 }
 
 impl FromStr for Node {
-    type Err = ParseFileErr;
+    type Err = Diagnostic<ParseFileErr>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Directory::from_str(s)
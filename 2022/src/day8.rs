@@ -1,6 +1,11 @@
 use std::collections::HashSet;
 
-use crate::{shared::PuzzleResult, test_solvers};
+use aoc_framework::{
+    generate::Rng,
+    diagnostic::Diagnostic,
+    shared::{PuzzleResult, Solution, SolveError},
+    test_solvers,
+};
 
 type TreeGrid = Vec<Vec<u8>>;
 
@@ -31,9 +36,9 @@ impl Direction {
 
         let mut next_position = Some(start_position);
 
-        while let Some((position, &tree_height)) = next_position.and_then(|position| {
-            tree_height(tree_grid, &position).and_then(|height| Some((position, height)))
-        }) {
+        while let Some((position, &tree_height)) = next_position
+            .and_then(|position| tree_height(tree_grid, &position).map(|height| (position, height)))
+        {
             if highest_tree_height
                 .map(|highest| tree_height > highest)
                 .unwrap_or(true)
@@ -56,9 +61,9 @@ impl Direction {
         let (x_modifier, y_modifier) = self.position_modifier();
         let mut next_position = start_position.add(x_modifier, y_modifier);
 
-        while let Some((position, &tree_height)) = next_position.and_then(|position| {
-            tree_height(tree_grid, &position).and_then(|height| Some((position, height)))
-        }) {
+        while let Some((position, &tree_height)) = next_position
+            .and_then(|position| tree_height(tree_grid, &position).map(|height| (position, height)))
+        {
             visible_positions.push(position);
 
             if tree_height >= start_position_height {
@@ -109,56 +114,79 @@ fn checked_add(a: usize, b: i32) -> Option<usize> {
     }
 }
 
-pub fn part1(input: &str) -> PuzzleResult {
-    let tree_grid = transform_input(input);
+pub struct Day8;
 
-    let (y, x) = grid_dimensions(&tree_grid);
+impl Solution for Day8 {
+    type Parsed = TreeGrid;
 
-    let horizontal_edges = 0..x;
-    let vertical_edges = 0..y;
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        transform_input(input)
+    }
 
-    let visible_from_top = horizontal_edges
-        .clone()
-        .flat_map(|x| Direction::Bottom.traverse_from_edge(&tree_grid, x));
+    fn part1(tree_grid: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        let (y, x) = grid_dimensions(tree_grid);
 
-    let visible_from_right = vertical_edges
-        .clone()
-        .flat_map(|y| Direction::Left.traverse_from_edge(&tree_grid, y));
+        let horizontal_edges = 0..x;
+        let vertical_edges = 0..y;
 
-    let visible_from_bottom =
-        horizontal_edges.flat_map(|x| Direction::Top.traverse_from_edge(&tree_grid, x));
+        let visible_from_top = horizontal_edges
+            .clone()
+            .flat_map(|x| Direction::Bottom.traverse_from_edge(tree_grid, x));
 
-    let visible_from_left =
-        vertical_edges.flat_map(|y| Direction::Right.traverse_from_edge(&tree_grid, y));
+        let visible_from_right = vertical_edges
+            .clone()
+            .flat_map(|y| Direction::Left.traverse_from_edge(tree_grid, y));
 
-    let mut visible_from_outside = HashSet::new();
-    visible_from_outside.extend(
-        visible_from_top
-            .chain(visible_from_right)
-            .chain(visible_from_bottom)
-            .chain(visible_from_left),
-    );
+        let visible_from_bottom =
+            horizontal_edges.flat_map(|x| Direction::Top.traverse_from_edge(tree_grid, x));
 
-    let result = visible_from_outside.len() as i64;
+        let visible_from_left =
+            vertical_edges.flat_map(|y| Direction::Right.traverse_from_edge(tree_grid, y));
 
-    result.into()
-}
+        let mut visible_from_outside = HashSet::new();
+        visible_from_outside.extend(
+            visible_from_top
+                .chain(visible_from_right)
+                .chain(visible_from_bottom)
+                .chain(visible_from_left),
+        );
+
+        let result = visible_from_outside.len();
 
-pub fn part2(input: &str) -> PuzzleResult {
-    let tree_grid = transform_input(input);
+        Ok(result.into())
+    }
+
+    fn part2(tree_grid: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        let (ys, xs) = grid_dimensions(tree_grid);
 
-    let (ys, xs) = grid_dimensions(&tree_grid);
+        let y_indices = 0..ys;
+        let x_indices = 0..xs;
+        let indices = y_indices.flat_map(|y| x_indices.clone().map(move |x| Position::new(x, y)));
 
-    let y_indices = 0..ys;
-    let x_indices = 0..xs;
-    let indices = y_indices.flat_map(|y| x_indices.clone().map(move |x| Position::new(x, y)));
+        let max_scenic_score = indices
+            .map(|pov| scenic_score(tree_grid, pov))
+            .max()
+            .expect("non-empty grid should have a max scenic score");
 
-    let max_scenic_score = indices
-        .map(|pov| scenic_score(&tree_grid, pov))
-        .max()
-        .expect("max scenic score should exist") as i64;
+        Ok(max_scenic_score.into())
+    }
 
-    max_scenic_score.into()
+    /// A forest of `size` by `size` trees.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let size = size.max(1);
+
+        Some(
+            (0..size)
+                .map(|_| {
+                    let mut row = (0..size)
+                        .map(|_| char::from_digit(rng.range(0..=9) as u32, 10).unwrap())
+                        .collect::<String>();
+                    row.push('\n');
+                    row
+                })
+                .collect(),
+        )
+    }
 }
 
 fn scenic_score(tree_grid: &TreeGrid, start_position: Position) -> u32 {
@@ -183,15 +211,39 @@ fn tree_height<'a>(tree_grid: &'a TreeGrid, position: &Position) -> Option<&'a u
         .and_then(|row| row.get(position.x))
 }
 
-fn transform_input(input: &str) -> TreeGrid {
-    input
+fn transform_input(input: &str) -> Result<TreeGrid, SolveError> {
+    let tree_grid = input
         .lines()
         .map(|line| {
-            line.split("")
-                .filter_map(|height| height.parse().ok())
-                .collect()
+            line.trim_end()
+                .char_indices()
+                .map(|(offset, height)| {
+                    height
+                        .to_digit(10)
+                        .map(|height| height as u8)
+                        .ok_or_else(|| {
+                            Diagnostic::new(
+                                "tree height should be a digit",
+                                input,
+                                &line[offset..offset + height.len_utf8()],
+                            )
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()
         })
-        .collect()
+        .collect::<Result<TreeGrid, _>>()?;
+
+    let width = tree_grid.first().map(|row| row.len()).unwrap_or(0);
+    if width == 0 {
+        return Err(SolveError::invalid_input("tree grid should not be empty"));
+    }
+    if tree_grid.iter().any(|row| row.len() != width) {
+        return Err(SolveError::invalid_input(
+            "all tree grid rows should have the same length",
+        ));
+    }
+
+    Ok(tree_grid)
 }
 
-test_solvers!(21, 8);
+test_solvers!(Day8);
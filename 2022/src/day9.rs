@@ -1,40 +1,63 @@
-mod rope;
+pub mod rope;
 
 use std::str::FromStr;
 
-use crate::{shared::PuzzleResult, test_solvers};
+use aoc_framework::{
+    generate::Rng,
+    shared::{PuzzleResult, Solution, SolveError},
+    test_solvers,
+};
 
 use rope::{Motion, Rope};
 
-pub fn part1(input: &str) -> PuzzleResult {
-    let motions = transform_input(input);
-    let mut rope = Rope::new(2);
+pub struct Day9;
 
-    for motion in &motions {
-        rope.move_head(*motion);
+impl Solution for Day9 {
+    type Parsed = Vec<Motion>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        transform_input(input)
     }
 
-    let result = rope.tail_positions().len() as i64;
-    result.into()
-}
+    fn part1(motions: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        let mut rope = Rope::new(2);
+
+        for motion in motions {
+            rope.move_head(*motion);
+        }
+
+        let result = rope.tail_positions().len();
+        Ok(result.into())
+    }
+
+    fn part2(motions: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+        let mut rope = Rope::new(10);
 
-pub fn part2(input: &str) -> PuzzleResult {
-    let motions = transform_input(input);
-    let mut rope = Rope::new(10);
+        for motion in motions {
+            rope.move_head(*motion);
+        }
 
-    for motion in &motions {
-        rope.move_head(*motion);
+        let result = rope.tail_positions().len();
+        Ok(result.into())
     }
 
-    let result = rope.tail_positions().len() as i64;
-    result.into()
+    /// `size` motions of 1 to 20 steps.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(
+            (0..size.max(1))
+                .map(|_| format!("{} {}\n", rng.choose(&["U", "R", "D", "L"]), rng.range(1..=20)))
+                .collect(),
+        )
+    }
 }
 
-fn transform_input(input: &str) -> Vec<Motion> {
-    input
+fn transform_input(input: &str) -> Result<Vec<Motion>, SolveError> {
+    let motions = input
         .lines()
-        .filter_map(|line| Motion::from_str(line).ok())
-        .collect::<Vec<_>>()
+        .map(|motion| Motion::from_str(motion).map_err(|err| err.relative_to(input, motion)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(motions)
 }
 
-test_solvers!(13, 1);
+test_solvers!(Day9);
@@ -1,4 +1,6 @@
-use std::{char::from_digit, collections::HashSet, fmt::Display, str::FromStr};
+use std::{char::from_digit, collections::HashSet, error::Error, fmt::Display, str::FromStr};
+
+use aoc_framework::diagnostic::Diagnostic;
 
 #[derive(Debug, Clone, Copy)]
 pub enum Direction {
@@ -9,7 +11,7 @@ pub enum Direction {
 }
 
 impl FromStr for Direction {
-    type Err = ParseMotionError;
+    type Err = Diagnostic<ParseMotionError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -17,7 +19,11 @@ impl FromStr for Direction {
             "R" => Ok(Self::Right),
             "D" => Ok(Self::Down),
             "L" => Ok(Self::Left),
-            _ => Err(ParseMotionError::InvalidDirectionError),
+            _ => Err(Diagnostic::new(
+                ParseMotionError::InvalidDirectionError,
+                s,
+                s,
+            )),
         }
     }
 }
@@ -37,7 +43,7 @@ impl Motion {
         self.length
     }
 
-    pub fn to_position(&self) -> Position {
+    pub fn to_position(self) -> Position {
         let motion_length = self.length() as i32;
         match self.direction {
             Direction::Up => Position::with_coordinates(0, -motion_length),
@@ -55,27 +61,40 @@ pub enum ParseMotionError {
     InvalidLengthError,
 }
 
+impl Display for ParseMotionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidPartsError => write!(f, "expected a '<direction> <length>' motion"),
+            Self::InvalidDirectionError => write!(f, "direction should be U, R, D or L"),
+            Self::InvalidLengthError => write!(f, "length should be a non-negative number"),
+        }
+    }
+}
+
+impl Error for ParseMotionError {}
+
 impl FromStr for Motion {
-    type Err = ParseMotionError;
+    type Err = Diagnostic<ParseMotionError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let parts = s.split(' ').collect::<Vec<_>>();
 
         match parts.as_slice() {
-            [direction, length] => {
-                let direction = Direction::from_str(direction)?;
-                let length = length
-                    .parse()
-                    .map_err(|_| ParseMotionError::InvalidLengthError)?;
+            &[direction, length] => {
+                let direction =
+                    Direction::from_str(direction).map_err(|err| err.relative_to(s, direction))?;
+                let length = length.parse().map_err(|_| {
+                    Diagnostic::new(ParseMotionError::InvalidLengthError, s, length)
+                })?;
 
                 Ok(Self { direction, length })
             }
-            _ => Err(ParseMotionError::InvalidPartsError),
+            _ => Err(Diagnostic::new(ParseMotionError::InvalidPartsError, s, s)),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct Position {
     x: i32,
     y: i32,
@@ -83,7 +102,7 @@ pub struct Position {
 
 impl Position {
     pub fn new() -> Self {
-        Self::with_coordinates(0, 0)
+        Self::default()
     }
 
     pub fn with_coordinates(x: i32, y: i32) -> Self {
@@ -133,7 +152,7 @@ impl Rope {
         }
     }
 
-    pub fn move_head(&mut self, motion: Motion) -> () {
+    pub fn move_head(&mut self, motion: Motion) {
         for _step in 0..motion.length() {
             let motion_step = Motion::new(motion.direction, 1);
             let head = self.positions[0];
@@ -151,7 +170,7 @@ impl Rope {
         }
     }
 
-    fn move_tail(&mut self, tail_index: usize) -> () {
+    fn move_tail(&mut self, tail_index: usize) {
         let head = self.positions[tail_index - 1];
         let tail = self.positions[tail_index];
 
@@ -0,0 +1,14 @@
+//! Solutions to the Advent of Code 2022 puzzles. Every `day<n>` module exposes its
+//! [`Solution`](aoc_framework::shared::Solution) and the domain types it is built on, and
+//! [`EDITION`] registers all of them with the framework.
+
+use aoc_framework::shared::{Edition, Solver};
+
+// day modules and their solvers
+include!("registry.rs");
+
+pub const EDITION: Edition = Edition {
+    year: 2022,
+    root: env!("CARGO_MANIFEST_DIR"),
+    solvers: SOLVERS,
+};
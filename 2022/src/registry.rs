@@ -0,0 +1,33 @@
+// Generated by the `new` command from the src/day<n>.rs files, do not edit by hand.
+// Included by lib.rs, so that the day modules are declared at the root of the crate.
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+
+/// Solvers of every implemented day, in day order.
+pub const SOLVERS: &[Solver] = &[
+    Solver::of::<day1::Day1>(),
+    Solver::of::<day2::Day2>(),
+    Solver::of::<day3::Day3>(),
+    Solver::of::<day4::Day4>(),
+    Solver::of::<day5::Day5>(),
+    Solver::of::<day6::Day6>(),
+    Solver::of::<day7::Day7>(),
+    Solver::of::<day8::Day8>(),
+    Solver::of::<day9::Day9>(),
+    Solver::of::<day10::Day10>(),
+    Solver::of::<day11::Day11>(),
+    Solver::of::<day12::Day12>(),
+    Solver::of::<day13::Day13>(),
+];
@@ -0,0 +1,323 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{
+    runner,
+    shared::{self, PuzzleResult},
+};
+
+/// Expected answers for one of a day's inputs, stored as `part<n>: <answer>` lines.
+/// Newlines in multi-line answers are escaped as `\n`.
+///
+/// Accepted answers for the real input live in `inputs/day<n>/answers.txt` of the year's crate,
+/// while every example `inputs/day<n>/<name>.txt` may have a `<name>.answers.txt` sidecar next to it.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    parts: [Option<String>; 2],
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong(String),
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "✓"),
+            Self::Wrong(_) => write!(f, "✗"),
+            Self::Unknown => write!(f, "?"),
+        }
+    }
+}
+
+impl Answers {
+    /// Path of the answers for the named input (`input` for the real one, `test` etc. for examples).
+    pub fn path(day_dir: &Path, input_name: &str) -> PathBuf {
+        let filename = match input_name {
+            "input" => "answers.txt".to_owned(),
+            example => format!("{example}.answers.txt"),
+        };
+
+        day_dir.join(filename)
+    }
+
+    /// Loads the answers for the named input, treating a missing file as having no answers yet.
+    pub fn load(day_dir: &Path, input_name: &str) -> io::Result<Self> {
+        Self::load_from(&Self::path(day_dir, input_name))
+    }
+
+    pub fn load_from(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::from_str(&content)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, day_dir: &Path, input_name: &str) -> io::Result<()> {
+        fs::write(Self::path(day_dir, input_name), self.to_string())
+    }
+
+    /// Returns the recorded answer for the part, in the form produced by `PuzzleResult`'s `Display`.
+    pub fn get(&self, part: usize) -> Option<&str> {
+        self.parts[part - 1].as_deref()
+    }
+
+    pub fn record(&mut self, part: usize, result: &PuzzleResult) {
+        self.parts[part - 1] = Some(result.to_string());
+    }
+
+    pub fn check(&self, part: usize, result: &PuzzleResult) -> Verdict {
+        match self.get(part) {
+            Some(expected) if *expected == result.to_string() => Verdict::Correct,
+            Some(expected) => Verdict::Wrong(expected.to_owned()),
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+/// Asserts that the solver still produces the recorded answer for the day's real input.
+///
+/// Days without a real input or a recorded answer are skipped. Used by the tests generated by
+/// `test_solvers!`.
+pub fn assert_recorded_answer(day_dir: &Path, part: usize, solver: &shared::Solver) {
+    let folder = day_dir.display();
+    let Ok(input) = shared::read_input(day_dir, "input") else {
+        println!("skipping {folder} part {part}: no real input");
+        return;
+    };
+    let answers = Answers::load(day_dir, "input").expect("answers file should be valid");
+    let Some(expected) = answers.get(part) else {
+        println!("skipping {folder} part {part}: no recorded answer");
+        return;
+    };
+
+    match solve(solver, part, &input) {
+        Ok(result) => assert_eq!(result.to_string(), expected),
+        Err(err) => panic!("{folder} part {part} failed on the real input: {err}"),
+    }
+}
+
+/// Asserts that the solver produces the expected answer for every example of the day that has one
+/// in its sidecar file, reporting all mismatching examples at once, including those whose solver
+/// panicked. Used by the tests generated by `test_solvers!`.
+pub fn assert_example_answers(day_dir: &Path, part: usize, solver: &shared::Solver) {
+    let folder = day_dir.display();
+    let examples = shared::example_names(day_dir).expect("inputs folder should be readable");
+    let mut mismatches = vec![];
+
+    for example in examples {
+        let answers = match Answers::load(day_dir, &example) {
+            Ok(answers) => answers,
+            Err(err) => {
+                mismatches.push(format!("{example}: could not load its answers: {err}"));
+                continue;
+            }
+        };
+        let Some(expected) = answers.get(part) else {
+            println!("skipping {folder}/{example} part {part}: no expected answer");
+            continue;
+        };
+
+        let input = shared::read_input(day_dir, &example).expect("example input should exist");
+        match solve(solver, part, &input).map(|result| result.to_string()) {
+            Ok(result) if result == expected => {}
+            Ok(result) => {
+                mismatches.push(format!("{example}: expected `{expected}`, got `{result}`"))
+            }
+            Err(err) => mismatches.push(format!(
+                "{example}: expected `{expected}`, got error: {err}"
+            )),
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "{folder} part {part} gave wrong answers for examples:\n{}",
+        mismatches.join("\n")
+    );
+}
+
+/// Solves the part, turning errors and panics into messages so that one example does not hide the
+/// others.
+fn solve(solver: &shared::Solver, part: usize, input: &str) -> Result<PuzzleResult, String> {
+    let (parsed, _) = runner::run_parse(solver, input);
+    let (outcome, _, _) = runner::run_part(solver, part, &parsed?);
+
+    outcome
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseAnswersError {
+    InvalidLineError(String),
+    InvalidPartError(String),
+}
+
+impl Display for ParseAnswersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLineError(line) => {
+                write!(f, "expected a 'part<n>: <answer>' line, got '{line}'")
+            }
+            Self::InvalidPartError(part) => write!(f, "expected part1 or part2, got '{part}'"),
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = ParseAnswersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Self::default();
+
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            let (part, answer) = line
+                .split_once(": ")
+                .ok_or_else(|| ParseAnswersError::InvalidLineError(line.to_owned()))?;
+
+            let part_index = match part {
+                "part1" => 0,
+                "part2" => 1,
+                _ => return Err(ParseAnswersError::InvalidPartError(part.to_owned())),
+            };
+
+            answers.parts[part_index] = Some(unescape(answer));
+        }
+
+        Ok(answers)
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, answer) in self.parts.iter().enumerate() {
+            if let Some(answer) = answer {
+                writeln!(f, "part{}: {}", index + 1, escape(answer))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+pub(crate) fn unescape(answer: &str) -> String {
+    let mut unescaped = String::with_capacity(answer.len());
+    let mut chars = answer.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                unescaped.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                unescaped.push('\\');
+                chars.next();
+            }
+            (c, _) => unescaped.push(c),
+        }
+    }
+
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, panic, str::FromStr};
+
+    use super::{assert_example_answers, Answers, Verdict};
+    use crate::{
+        shared::{PuzzleResult, Solution, SolveError, Solver},
+        testing::TempDir,
+    };
+
+    #[test]
+    fn answers_round_trip_through_the_file_format() {
+        let mut answers = Answers::default();
+        answers.record(1, &PuzzleResult::from(42));
+        answers.record(2, &PuzzleResult::from("\n#.\\\n.#\n".to_owned()));
+
+        let content = answers.to_string();
+
+        assert_eq!(content, "part1: 42\npart2: \\n#.\\\\\\n.#\\n\n");
+        assert_eq!(Answers::from_str(&content), Ok(answers));
+    }
+
+    #[test]
+    fn check_compares_against_recorded_answer() {
+        let answers = Answers::from_str("part2: 13\n").unwrap();
+
+        assert_eq!(answers.check(1, &PuzzleResult::from(13)), Verdict::Unknown);
+        assert_eq!(answers.check(2, &PuzzleResult::from(13)), Verdict::Correct);
+        assert_eq!(
+            answers.check(2, &PuzzleResult::from(14)),
+            Verdict::Wrong("13".to_owned())
+        );
+    }
+
+    /// Counts lines, panicking on empty ones.
+    struct Lines;
+
+    impl Solution for Lines {
+        type Parsed = Vec<String>;
+
+        fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+            Ok(input.lines().map(str::to_owned).collect())
+        }
+
+        fn part1(lines: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+            assert!(lines.iter().all(|line| !line.is_empty()), "empty line");
+
+            Ok(lines.len().into())
+        }
+
+        fn part2(lines: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+            Ok(lines.len().into())
+        }
+    }
+
+    #[test]
+    fn every_failing_example_is_reported() {
+        let temp_dir = TempDir::new("answers-test");
+        let day_dir = temp_dir.path();
+        for (example, input, expected) in [
+            ("test", "a\nb\n", "2"),
+            ("test2", "a\n\nb\n", "3"),
+            ("test3", "a\n", "2"),
+        ] {
+            fs::write(day_dir.join(format!("{example}.txt")), input).unwrap();
+            fs::write(
+                day_dir.join(format!("{example}.answers.txt")),
+                format!("part1: {expected}\n"),
+            )
+            .unwrap();
+        }
+
+        let solver = Solver::of::<Lines>();
+        let failure = panic::catch_unwind(|| assert_example_answers(day_dir, 1, &solver));
+
+        let payload = failure.expect_err("wrong examples should fail the assertion");
+        let message = payload.downcast_ref::<String>().unwrap();
+        assert!(!message.contains("test:"), "{message}");
+        assert!(
+            message.contains("test2: expected `3`, got error: empty line"),
+            "{message}"
+        );
+        assert!(
+            message.contains("test3: expected `2`, got `1`"),
+            "{message}"
+        );
+    }
+}
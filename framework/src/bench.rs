@@ -0,0 +1,281 @@
+use std::{
+    fmt::Display,
+    num::ParseIntError,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    memory::{self, AllocStats},
+    shared::{PuzzleResult, SolveError, Solver},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchOptions {
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+impl BenchOptions {
+    pub const DEFAULT_WARMUP: usize = 10;
+    pub const DEFAULT_ITERATIONS: usize = 100;
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            warmup: Self::DEFAULT_WARMUP,
+            iterations: Self::DEFAULT_ITERATIONS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted_samples = samples.to_vec();
+        sorted_samples.sort();
+
+        let middle = sorted_samples.len() / 2;
+        let median = if sorted_samples.len().is_multiple_of(2) {
+            (sorted_samples[middle - 1] + sorted_samples[middle]) / 2
+        } else {
+            sorted_samples[middle]
+        };
+
+        let seconds = samples
+            .iter()
+            .map(Duration::as_secs_f64)
+            .collect::<Vec<_>>();
+        let mean = seconds.iter().sum::<f64>() / seconds.len() as f64;
+        let variance = seconds
+            .iter()
+            .map(|sample| (sample - mean).powi(2))
+            .sum::<f64>()
+            / seconds.len() as f64;
+
+        Some(Self {
+            min: sorted_samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// What a benchmark measures: parsing the input or solving a part of the parsed input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Part(usize),
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse => write!(f, "parse"),
+            Self::Part(part) => write!(f, "{part}"),
+        }
+    }
+}
+
+impl FromStr for Step {
+    type Err = ParseIntError;
+
+    /// Reads a step as displayed: `parse` or the number of the part.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Self::Parse),
+            part => part.parse().map(Self::Part),
+        }
+    }
+}
+
+pub struct BenchResult {
+    pub day: usize,
+    pub step: Step,
+    /// The answer of a part, `None` for the parse step.
+    pub answer: Option<PuzzleResult>,
+    pub stats: Stats,
+    /// What one run of the step allocated, when the binary counts allocations.
+    pub alloc: Option<AllocStats>,
+}
+
+/// Benchmarks parsing the input, then each of the parts on the parsed input. Every step is run
+/// `options.warmup` times untimed, then timed over `options.iterations` runs. Allocations are
+/// counted on one more untimed run.
+///
+/// The input is read by the caller, so only the solver itself is measured. The first error the
+/// solver returns stops the benchmark.
+pub fn bench_solver(
+    day: usize,
+    solver: &Solver,
+    parts: &[usize],
+    input: &str,
+    options: &BenchOptions,
+) -> Result<Vec<BenchResult>, SolveError> {
+    let (parsed, stats, alloc) = measure(options, || (solver.parse)(input))?;
+    let mut results = vec![BenchResult {
+        day,
+        step: Step::Parse,
+        answer: None,
+        stats,
+        alloc,
+    }];
+
+    for &part in parts {
+        let (answer, stats, alloc) = measure(options, || (solver.parts[part - 1])(&parsed))?;
+
+        results.push(BenchResult {
+            day,
+            step: Step::Part(part),
+            answer: Some(answer),
+            stats,
+            alloc,
+        });
+    }
+
+    Ok(results)
+}
+
+/// Runs `f` once counting its allocations and `options.warmup` times untimed, then times
+/// `options.iterations` runs, returning the value of the last one.
+fn measure<T>(
+    options: &BenchOptions,
+    mut f: impl FnMut() -> Result<T, SolveError>,
+) -> Result<(T, Stats, Option<AllocStats>), SolveError> {
+    let (value, alloc) = memory::measure(&mut f);
+    value?;
+
+    for _ in 0..options.warmup {
+        f()?;
+    }
+
+    let mut value = None;
+    let mut samples = Vec::with_capacity(options.iterations);
+
+    for _ in 0..options.iterations {
+        let start = Instant::now();
+        let iteration_value = f();
+        samples.push(start.elapsed());
+
+        value = Some(iteration_value?);
+    }
+
+    Ok((
+        value.expect("solver should run at least once"),
+        Stats::from_samples(&samples).expect("there should be at least one sample"),
+        alloc,
+    ))
+}
+
+pub fn print_report(results: &[BenchResult], options: &BenchOptions) {
+    println!(
+        "{} warm-up and {} timed runs per solver",
+        options.warmup, options.iterations
+    );
+    println!();
+
+    let answers = results
+        .iter()
+        .map(|result| {
+            // multi-line answers (e.g. CRT screens) are too tall for a table row
+            let answer = result
+                .answer
+                .as_ref()
+                .map(|answer| answer.to_string())
+                .unwrap_or_default();
+            match answer.lines().filter(|line| !line.is_empty()).count() {
+                0 | 1 => answer.trim().to_owned(),
+                lines => format!("({lines} lines)"),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let answer_width = answers
+        .iter()
+        .map(|answer| answer.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+
+    // allocation columns are only shown when the binary counts allocations
+    let show_alloc = results.iter().any(|result| result.alloc.is_some());
+    let (alloc_header, alloc_separator) = if show_alloc {
+        (
+            format!(" | {:>10} | {:>12} | {:>12}", "Allocs", "Allocated", "Peak"),
+            format!("-+-{:-<10}-+-{:-<12}-+-{:-<12}", "", "", ""),
+        )
+    } else {
+        (String::new(), String::new())
+    };
+
+    println!(
+        "{:>3} | {:>5} | {:<answer_width$} | {:>12} | {:>12} | {:>12} | {:>12}{alloc_header}",
+        "Day", "Step", "Answer", "Min", "Median", "Mean", "Std dev"
+    );
+    println!(
+        "{:-<3}-+-{:-<5}-+-{:-<answer_width$}-+-{:-<12}-+-{:-<12}-+-{:-<12}-+-{:-<12}{alloc_separator}",
+        "", "", "", "", "", "", ""
+    );
+
+    for (result, answer) in results.iter().zip(answers) {
+        let stats = &result.stats;
+        let alloc_columns = match (show_alloc, result.alloc) {
+            (true, Some(alloc)) => format!(
+                " | {:>10} | {:>12} | {:>12}",
+                alloc.allocations,
+                memory::format_bytes(alloc.bytes),
+                memory::format_bytes(alloc.peak_live_bytes)
+            ),
+            (true, None) => format!(" | {:>10} | {:>12} | {:>12}", "", "", ""),
+            (false, _) => String::new(),
+        };
+
+        println!(
+            "{:>3} | {:>5} | {:<answer_width$} | {:>12} | {:>12} | {:>12} | {:>12}{alloc_columns}",
+            result.day,
+            result.step.to_string(),
+            answer,
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.mean),
+            format!("{:.2?}", stats.std_dev),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    #[test]
+    fn stats_are_computed_from_samples() {
+        let samples = [4, 1, 3, 2].map(Duration::from_millis);
+
+        let stats = Stats::from_samples(&samples).unwrap();
+
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert_eq!(stats.std_dev.as_micros(), 1118);
+    }
+
+    #[test]
+    fn stats_need_at_least_one_sample() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+}
@@ -0,0 +1,293 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::bench::{BenchResult, Step};
+
+/// Median of one benchmarked step, as recorded after a benchmark.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchRecord {
+    pub recorded_at: u64,
+    /// Short hash of the commit the benchmark ran on, when known.
+    pub commit: Option<String>,
+    pub day: usize,
+    pub step: Step,
+    pub median: Duration,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseBenchHistoryError(String);
+
+impl Display for ParseBenchHistoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid benchmark record '{}'", self.0)
+    }
+}
+
+/// Benchmarks of the real inputs of a year's crate, stored in `bench_history.txt` at its root as
+/// `<unix time>\t<commit>\t<day>\t<step>\t<median ns>` lines, oldest first. The commit is `-` when
+/// unknown.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct BenchHistory {
+    records: Vec<BenchRecord>,
+}
+
+impl BenchHistory {
+    pub fn path(root: &Path) -> PathBuf {
+        root.join("bench_history.txt")
+    }
+
+    /// Loads the history of the crate, treating a missing file as no benchmark yet.
+    pub fn load(root: &Path) -> io::Result<Self> {
+        match fs::read_to_string(Self::path(root)) {
+            Ok(content) => Self::from_str(&content)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, root: &Path) -> io::Result<()> {
+        fs::write(Self::path(root), self.to_string())
+    }
+
+    pub fn records(&self) -> &[BenchRecord] {
+        &self.records
+    }
+
+    /// Appends the medians of the results.
+    pub fn record(&mut self, results: &[BenchResult], commit: Option<&str>, now: u64) {
+        self.records
+            .extend(results.iter().map(|result| BenchRecord {
+                recorded_at: now,
+                commit: commit.map(str::to_owned),
+                day: result.day,
+                step: result.step,
+                median: result.stats.median,
+            }));
+    }
+
+    /// The latest record of the step, which later benchmarks are compared to.
+    pub fn baseline(&self, day: usize, step: Step) -> Option<&BenchRecord> {
+        self.records
+            .iter()
+            .rev()
+            .find(|record| record.day == day && record.step == step)
+    }
+}
+
+impl FromStr for BenchHistory {
+    type Err = ParseBenchHistoryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let records = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| parse_record(line).ok_or_else(|| ParseBenchHistoryError(line.to_owned())))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { records })
+    }
+}
+
+fn parse_record(line: &str) -> Option<BenchRecord> {
+    let mut fields = line.split('\t');
+
+    let recorded_at = fields.next()?.parse().ok()?;
+    let commit = match fields.next()? {
+        "-" => None,
+        commit => Some(commit.to_owned()),
+    };
+    let day = fields.next()?.parse().ok()?;
+    let step = fields.next()?.parse().ok()?;
+    let median = Duration::from_nanos(fields.next()?.parse().ok()?);
+
+    if fields.next().is_some() {
+        return None;
+    }
+
+    Some(BenchRecord {
+        recorded_at,
+        commit,
+        day,
+        step,
+        median,
+    })
+}
+
+impl Display for BenchHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for record in &self.records {
+            writeln!(
+                f,
+                "{}\t{}\t{}\t{}\t{}",
+                record.recorded_at,
+                record.commit.as_deref().unwrap_or("-"),
+                record.day,
+                record.step,
+                record.median.as_nanos()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Median of a step against its baseline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    pub day: usize,
+    pub step: Step,
+    pub baseline: Duration,
+    pub median: Duration,
+}
+
+impl Comparison {
+    /// Change of the median in percent, positive when slower.
+    pub fn change_percent(&self) -> f64 {
+        let baseline = self.baseline.as_secs_f64();
+        if baseline == 0.0 {
+            return 0.0;
+        }
+
+        (self.median.as_secs_f64() - baseline) / baseline * 100.0
+    }
+
+    /// Whether the step got slower than its baseline by more than `threshold_percent`.
+    pub fn is_regression(&self, threshold_percent: usize) -> bool {
+        self.change_percent() > threshold_percent as f64
+    }
+}
+
+/// Compares the results with their baselines in the history. Steps never benchmarked before are
+/// left out.
+pub fn compare(history: &BenchHistory, results: &[BenchResult]) -> Vec<Comparison> {
+    results
+        .iter()
+        .filter_map(|result| {
+            let baseline = history.baseline(result.day, result.step)?;
+
+            Some(Comparison {
+                day: result.day,
+                step: result.step,
+                baseline: baseline.median,
+                median: result.stats.median,
+            })
+        })
+        .collect()
+}
+
+pub fn print_comparison(comparisons: &[Comparison], threshold_percent: usize) {
+    println!(
+        "{:>3} | {:>5} | {:>12} | {:>12} | {:>8} | Status",
+        "Day", "Step", "Baseline", "Median", "Change"
+    );
+    println!(
+        "{:-<3}-+-{:-<5}-+-{:-<12}-+-{:-<12}-+-{:-<8}-+-{:-<10}",
+        "", "", "", "", "", ""
+    );
+
+    for comparison in comparisons {
+        let status = if comparison.is_regression(threshold_percent) {
+            "REGRESSION"
+        } else {
+            "ok"
+        };
+
+        println!(
+            "{:>3} | {:>5} | {:>12} | {:>12} | {:>7.1}% | {status}",
+            comparison.day,
+            comparison.step.to_string(),
+            format!("{:.2?}", comparison.baseline),
+            format!("{:.2?}", comparison.median),
+            comparison.change_percent(),
+        );
+    }
+}
+
+/// Short hash of the commit checked out in the repository holding `root`, if git can tell.
+pub fn current_commit(root: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(root)
+        .output()
+        .ok()?;
+
+    let commit = String::from_utf8(output.stdout).ok()?.trim().to_owned();
+
+    (output.status.success() && !commit.is_empty()).then_some(commit)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, time::Duration};
+
+    use super::{compare, BenchHistory, BenchRecord, ParseBenchHistoryError};
+    use crate::bench::{BenchResult, Stats, Step};
+
+    fn result(day: usize, step: Step, median_ms: u64) -> BenchResult {
+        let median = Duration::from_millis(median_ms);
+
+        BenchResult {
+            day,
+            step,
+            answer: None,
+            stats: Stats::from_samples(&[median]).unwrap(),
+            alloc: None,
+        }
+    }
+
+    #[test]
+    fn history_is_saved_and_loaded_line_by_line() {
+        let mut history = BenchHistory::default();
+        history.record(&[result(11, Step::Parse, 2)], Some("a6a7190"), 1700000000);
+        history.record(&[result(11, Step::Part(2), 120)], None, 1700000100);
+
+        let content = history.to_string();
+
+        assert_eq!(
+            content,
+            "1700000000\ta6a7190\t11\tparse\t2000000\n1700000100\t-\t11\t2\t120000000\n"
+        );
+        assert_eq!(BenchHistory::from_str(&content), Ok(history));
+        assert_eq!(
+            BenchHistory::from_str("1700000000\ta6a7190\t11\tthree\t2000000"),
+            Err(ParseBenchHistoryError(
+                "1700000000\ta6a7190\t11\tthree\t2000000".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn results_are_compared_to_the_latest_record() {
+        let mut history = BenchHistory::default();
+        history.record(&[result(6, Step::Part(1), 10)], None, 1);
+        history.record(&[result(6, Step::Part(1), 4)], None, 2);
+
+        let comparisons = compare(
+            &history,
+            &[result(6, Step::Part(1), 5), result(6, Step::Part(2), 9)],
+        );
+
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].baseline, Duration::from_millis(4));
+        assert_eq!(comparisons[0].change_percent(), 25.0);
+        assert!(comparisons[0].is_regression(10));
+        assert!(!comparisons[0].is_regression(25));
+        assert_eq!(
+            history.baseline(6, Step::Part(1)),
+            Some(&BenchRecord {
+                recorded_at: 2,
+                commit: None,
+                day: 6,
+                step: Step::Part(1),
+                median: Duration::from_millis(4),
+            })
+        );
+    }
+}
@@ -0,0 +1,337 @@
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::shared;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable holding the session cookie, taking precedence over the config file.
+pub const SESSION_ENV_VAR: &str = "AOC_SESSION";
+/// Environment variable overriding the base URL, e.g. to use a local stand-in server.
+pub const BASE_URL_ENV_VAR: &str = "AOC_BASE_URL";
+
+/// Identifies the tool to the Advent of Code servers with a way to reach its author, as asked by
+/// their maintainer.
+pub const USER_AGENT: &str = concat!(
+    "aoc-framework/",
+    env!("CARGO_PKG_VERSION"),
+    " (+",
+    env!("CARGO_PKG_REPOSITORY"),
+    "; personal Advent of Code runner, inputs are cached locally)"
+);
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSessionError,
+    /// The server answered with an error status, along with the first line of its body.
+    HttpError(u16, String),
+    TransportError(String),
+    IoError(PathBuf, io::Error),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingSessionError => write!(
+                f,
+                "missing session token, set {SESSION_ENV_VAR} or write it to {}",
+                session_config_path()
+                    .map(|path| path.display().to_string())
+                    .unwrap_or_else(|| "~/.config/aoc/session".to_owned())
+            ),
+            Self::HttpError(status, message) if message.is_empty() => {
+                write!(f, "server answered with status {status}")
+            }
+            Self::HttpError(status, message) => {
+                write!(f, "server answered with status {status}: {message}")
+            }
+            Self::TransportError(message) => write!(f, "request failed: {message}"),
+            Self::IoError(path, err) => write!(f, "could not access {}: {err}", path.display()),
+        }
+    }
+}
+
+impl Error for ClientError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::IoError(_, err) => Some(err),
+            Self::MissingSessionError | Self::HttpError(..) | Self::TransportError(_) => None,
+        }
+    }
+}
+
+/// Client for the Advent of Code website, authenticated with the session cookie of a logged-in
+/// browser.
+pub struct Client {
+    base_url: String,
+    session: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: Option<String>) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session,
+            agent,
+        }
+    }
+
+    /// Creates a client for the base URL in `AOC_BASE_URL`, if set, and the session token in
+    /// `AOC_SESSION` or in the config file. A missing token is only reported once a request needs it.
+    pub fn from_env() -> Result<Self, ClientError> {
+        let base_url = env::var(BASE_URL_ENV_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_owned());
+
+        Ok(Self::new(&base_url, read_session()?))
+    }
+
+    /// Sends an authenticated GET request for `path`, returning the body of the response.
+    pub fn get(&self, path: &str) -> Result<String, ClientError> {
+        let request = self.authenticated(self.agent.get(&format!("{}{path}", self.base_url)))?;
+
+        read_response(request.call())
+    }
+
+    /// Sends an authenticated form POST request to `path`, returning the body of the response.
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String, ClientError> {
+        let request = self.authenticated(self.agent.post(&format!("{}{path}", self.base_url)))?;
+
+        read_response(request.send_form(form))
+    }
+
+    fn authenticated(&self, request: ureq::Request) -> Result<ureq::Request, ClientError> {
+        let session = self
+            .session
+            .as_deref()
+            .ok_or(ClientError::MissingSessionError)?;
+
+        Ok(request.set("Cookie", &format!("session={session}")))
+    }
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, ClientError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|err| ClientError::TransportError(err.to_string())),
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            let message = body.lines().next().unwrap_or("").trim().to_owned();
+
+            Err(ClientError::HttpError(status, message))
+        }
+        Err(ureq::Error::Transport(err)) => Err(ClientError::TransportError(err.to_string())),
+    }
+}
+
+/// Path of the config file holding the session token: `aoc/session` in `$XDG_CONFIG_HOME`, or in
+/// `~/.config` when it is not set.
+pub fn session_config_path() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+
+    Some(config_dir.join("aoc").join("session"))
+}
+
+fn read_session() -> Result<Option<String>, ClientError> {
+    if let Ok(session) = env::var(SESSION_ENV_VAR) {
+        return Ok(non_empty(&session));
+    }
+
+    let Some(path) = session_config_path() else {
+        return Ok(None);
+    };
+
+    match fs::read_to_string(&path) {
+        Ok(session) => Ok(non_empty(&session)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(ClientError::IoError(path, err)),
+    }
+}
+
+fn non_empty(session: &str) -> Option<String> {
+    Some(session.trim().to_owned()).filter(|session| !session.is_empty())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FetchOutcome {
+    Downloaded(PathBuf),
+    /// The input was already there, and was not downloaded again.
+    Cached(PathBuf),
+}
+
+/// Downloads the real input of the day into `inputs/day<n>/input.txt`, unless it is already
+/// there. Empty files, as created by the `new` command, do not count as cached.
+pub fn fetch_input(
+    client: &Client,
+    year: u16,
+    day: usize,
+    day_dir: &Path,
+) -> Result<FetchOutcome, ClientError> {
+    let path = shared::input_path(day_dir, "input");
+
+    match fs::metadata(&path) {
+        Ok(metadata) if metadata.len() > 0 => return Ok(FetchOutcome::Cached(path)),
+        Ok(_) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(ClientError::IoError(path, err)),
+    }
+
+    let input = client.get(&format!("/{year}/day/{day}/input"))?;
+
+    // written aside then renamed, as an interrupted write would leave a truncated input that is
+    // then taken as cached
+    let partial_path = path.with_extension("partial");
+    fs::create_dir_all(day_dir).map_err(|err| ClientError::IoError(day_dir.to_owned(), err))?;
+    fs::write(&partial_path, input)
+        .map_err(|err| ClientError::IoError(partial_path.clone(), err))?;
+    fs::rename(&partial_path, &path).map_err(|err| ClientError::IoError(path.clone(), err))?;
+
+    Ok(FetchOutcome::Downloaded(path))
+}
+
+/// A stand-in HTTP server for tests, answering requests with canned responses and recording them.
+#[cfg(test)]
+pub(crate) mod test_server {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    /// Serves one `(status, body)` response per incoming request, then stops. Returns the base URL
+    /// of the server and a receiver of the raw requests it got.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("test server should bind");
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let Ok((stream, _)) = listener.accept() else {
+                    return;
+                };
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                        break;
+                    }
+                    if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = length.trim().parse().unwrap_or(0);
+                    }
+                    request.push_str(&line);
+                }
+
+                let mut body_bytes = vec![0; content_length];
+                let _ = reader.read_exact(&mut body_bytes);
+                request.push_str("\r\n");
+                request.push_str(&String::from_utf8_lossy(&body_bytes));
+
+                let _ = write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 {status} Canned\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = sender.send(request);
+            }
+        });
+
+        (base_url, receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, fs, io, path::PathBuf};
+
+    use super::{fetch_input, test_server, Client, ClientError, FetchOutcome, USER_AGENT};
+    use crate::testing::TempDir;
+
+    #[test]
+    fn input_is_downloaded_once_then_cached() {
+        let temp_dir = TempDir::new("fetch");
+        let day_dir = temp_dir.path();
+        let (base_url, requests) = test_server::serve(vec![(200, "1\n2\n3\n")]);
+        let client = Client::new(&base_url, Some("secret".to_owned()));
+
+        let downloaded = fetch_input(&client, 2022, 1, day_dir).unwrap();
+        let cached = fetch_input(&client, 2022, 1, day_dir).unwrap();
+
+        let input_path = day_dir.join("input.txt");
+        assert_eq!(downloaded, FetchOutcome::Downloaded(input_path.clone()));
+        assert_eq!(cached, FetchOutcome::Cached(input_path.clone()));
+        assert_eq!(fs::read_to_string(&input_path).unwrap(), "1\n2\n3\n");
+        assert!(!day_dir.join("input.partial").exists());
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2022/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=secret\r\n"));
+        assert!(request.contains(&format!("User-Agent: {USER_AGENT}\r\n")));
+        assert!(USER_AGENT.contains("github.com/Groszczu/advent-of-code"));
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[test]
+    fn error_status_is_reported_without_writing_the_input() {
+        let temp_dir = TempDir::new("fetch-locked");
+        let day_dir = temp_dir.path();
+        let (base_url, _requests) = test_server::serve(vec![(
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks!\n",
+        )]);
+        let client = Client::new(&base_url, Some("secret".to_owned()));
+
+        let err = fetch_input(&client, 2022, 25, day_dir).unwrap_err();
+
+        assert!(matches!(err, ClientError::HttpError(404, _)));
+        assert_eq!(
+            err.to_string(),
+            "server answered with status 404: Please don't repeatedly request this endpoint before it unlocks!"
+        );
+        assert!(!day_dir.join("input.txt").exists());
+    }
+
+    #[test]
+    fn requests_need_a_session() {
+        let client = Client::new("http://127.0.0.1:9", None);
+
+        assert!(matches!(
+            client.get("/2022/day/1/input"),
+            Err(ClientError::MissingSessionError)
+        ));
+    }
+
+    #[test]
+    fn io_errors_are_the_source_of_client_errors() {
+        let err = ClientError::IoError(
+            PathBuf::from("input.txt"),
+            io::Error::from(io::ErrorKind::PermissionDenied),
+        );
+
+        let source = err
+            .source()
+            .and_then(|source| source.downcast_ref::<io::Error>());
+        assert_eq!(
+            source.map(io::Error::kind),
+            Some(io::ErrorKind::PermissionDenied)
+        );
+        assert!(ClientError::MissingSessionError.source().is_none());
+    }
+}
@@ -0,0 +1,166 @@
+use std::{error::Error, fmt::Display};
+
+use crate::shared::SolveError;
+
+/// A parse error pointing at the offending part of the input.
+///
+/// Parsers create it against the text they were given, and callers holding the whole input move it
+/// with [`Diagnostic::relative_to`] so that line and column refer to the input file. It is rendered
+/// with the offending line and a caret under the span:
+///
+/// ```text
+/// expected a '<start>-<end>' section
+///  --> line 2, column 5
+///   |
+/// 2 | 5-7,7x9
+///   |     ^^^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic<E> {
+    pub kind: E,
+    /// 1-based line of the start of the span.
+    pub line: usize,
+    /// 1-based column of the start of the span, counted in characters.
+    pub column: usize,
+    /// The whole line the span starts on.
+    snippet: String,
+    /// Number of carets, i.e. the characters of the span that are on its first line.
+    width: usize,
+    /// Byte range of the span within the text the diagnostic was created against.
+    offset: usize,
+    len: usize,
+}
+
+impl<E> Diagnostic<E> {
+    /// Creates a diagnostic for `span`, which should be a slice of `source` (as returned by
+    /// `lines`, `split`, `trim` and the like). Any other span makes it point at the whole source.
+    pub fn new(kind: E, source: &str, span: &str) -> Self {
+        match offset_in(source, span) {
+            Some(offset) => Self::at(kind, source, offset, span.len()),
+            None => Self::at(kind, source, 0, source.len()),
+        }
+    }
+
+    /// Creates a diagnostic for the single character starting at byte `offset` of `source`, or
+    /// for the end of `source` when the offset is past its last character.
+    pub fn at_char(kind: E, source: &str, offset: usize) -> Self {
+        let len = source[offset..]
+            .chars()
+            .next()
+            .map(char::len_utf8)
+            .unwrap_or(0);
+
+        Self::at(kind, source, offset, len)
+    }
+
+    /// Moves a diagnostic created against `fragment` so that it refers to `input`, which
+    /// `fragment` is a slice of.
+    pub fn relative_to(self, input: &str, fragment: &str) -> Self {
+        match offset_in(input, fragment) {
+            Some(fragment_offset) => {
+                Self::at(self.kind, input, fragment_offset + self.offset, self.len)
+            }
+            None => self,
+        }
+    }
+
+    fn at(kind: E, source: &str, offset: usize, len: usize) -> Self {
+        let line_start = source[..offset].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = source[offset..]
+            .find('\n')
+            .map_or(source.len(), |index| offset + index);
+
+        let width = source[offset..(offset + len).min(line_end)]
+            .chars()
+            .count()
+            .max(1);
+
+        Self {
+            kind,
+            line: source[..offset].matches('\n').count() + 1,
+            column: source[line_start..offset].chars().count() + 1,
+            snippet: source[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_owned(),
+            width,
+            offset,
+            len,
+        }
+    }
+}
+
+impl<E: Display> Display for Diagnostic<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let line_number = self.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+
+        writeln!(f, "{}", self.kind)?;
+        writeln!(f, "{gutter}--> line {}, column {}", self.line, self.column)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{line_number} | {}", self.snippet)?;
+        write!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(self.column - 1),
+            "^".repeat(self.width)
+        )
+    }
+}
+
+impl<E: Display + std::fmt::Debug> Error for Diagnostic<E> {}
+
+impl<E: Display + std::fmt::Debug + Send + Sync + 'static> From<Diagnostic<E>> for SolveError {
+    fn from(err: Diagnostic<E>) -> Self {
+        Self::ParseError(Box::new(err))
+    }
+}
+
+/// Byte offset of `span` within `source`, if it is a slice of it.
+fn offset_in(source: &str, span: &str) -> Option<usize> {
+    let source_start = source.as_ptr() as usize;
+    let span_start = span.as_ptr() as usize;
+
+    (source_start <= span_start && span_start + span.len() <= source_start + source.len())
+        .then(|| span_start - source_start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Diagnostic;
+
+    #[test]
+    fn diagnostic_points_at_the_span_with_carets() {
+        let source = "1-2,3-4\n5-7,7x9\n";
+        let span = &source[12..15];
+
+        let diagnostic = Diagnostic::new("expected a section range", source, span);
+
+        assert_eq!((diagnostic.line, diagnostic.column), (2, 5));
+        assert_eq!(
+            diagnostic.to_string(),
+            "expected a section range\n --> line 2, column 5\n  |\n2 | 5-7,7x9\n  |     ^^^"
+        );
+    }
+
+    #[test]
+    fn diagnostic_can_be_moved_into_the_whole_input() {
+        let input = "1-2,3-4\n5-7,7x9\n";
+        let line = input.lines().nth(1).unwrap();
+        let (_, section) = line.split_once(',').unwrap();
+
+        let diagnostic = Diagnostic::at_char("unexpected character", section, 1);
+        assert_eq!((diagnostic.line, diagnostic.column), (1, 2));
+
+        let diagnostic = diagnostic.relative_to(input, section);
+        assert_eq!((diagnostic.line, diagnostic.column), (2, 6));
+        assert!(diagnostic.to_string().ends_with("2 | 5-7,7x9\n  |      ^"));
+    }
+
+    #[test]
+    fn diagnostic_at_end_of_input_points_past_the_last_character() {
+        let diagnostic = Diagnostic::at_char("unclosed list", "[1,[2]", 6);
+
+        assert_eq!((diagnostic.line, diagnostic.column), (1, 7));
+        assert!(diagnostic.to_string().ends_with("1 | [1,[2]\n  |       ^"));
+    }
+}
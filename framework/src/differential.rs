@@ -0,0 +1,256 @@
+use std::{fmt::Display, ops::Range};
+
+use crate::{
+    generate, runner,
+    shared::{PuzzleResult, ReferenceSolver, Solver},
+};
+
+/// A generated input on which a part of a solution disagrees with its reference.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch {
+    pub seed: u64,
+    /// Size the input was generated with, before shrinking removed parts of it.
+    pub size: usize,
+    pub part: usize,
+    pub input: String,
+    pub solution: Result<PuzzleResult, String>,
+    pub reference: Result<PuzzleResult, String>,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let show = |outcome: &Result<PuzzleResult, String>| match outcome {
+            Ok(result) => result.to_string(),
+            Err(err) => format!("error: {err}"),
+        };
+
+        writeln!(
+            f,
+            "part {} disagrees with its reference on the input generated for seed {} and size {}:",
+            self.part, self.seed, self.size
+        )?;
+        write!(f, "{}", self.input)?;
+        if !self.input.ends_with('\n') {
+            writeln!(f)?;
+        }
+        writeln!(f, "solution:  {}", show(&self.solution))?;
+        write!(f, "reference: {}", show(&self.reference))
+    }
+}
+
+/// Parts of the solver that have a reference solution.
+pub fn references(solver: &Solver) -> impl Iterator<Item = (usize, ReferenceSolver)> {
+    (1..)
+        .zip(solver.references)
+        .filter_map(|(part, reference)| Some((part, reference?)))
+}
+
+type Outcomes = (Result<PuzzleResult, String>, Result<PuzzleResult, String>);
+
+/// Outcomes of the part and its reference on the input when they disagree. Both failing is not a
+/// disagreement: the input is invalid for both.
+fn disagreement(
+    solver: &Solver,
+    part: usize,
+    reference: ReferenceSolver,
+    input: &str,
+) -> Option<Outcomes> {
+    let solution = match runner::run_parse(solver, input).0 {
+        Ok(parsed) => runner::run_part(solver, part, &parsed).0,
+        Err(err) => Err(err),
+    };
+    let reference = runner::run_reference(reference, input);
+
+    match (&solution, &reference) {
+        (Ok(solution), Ok(reference)) if solution == reference => None,
+        (Err(_), Err(_)) => None,
+        _ => Some((solution, reference)),
+    }
+}
+
+/// Runs the parts having a reference and their references on the input generated for each seed,
+/// returning the first disagreement. Solvers without a generator have no input to check.
+pub fn find_mismatch(solver: &Solver, seeds: Range<u64>, size: usize) -> Option<Mismatch> {
+    for seed in seeds {
+        let input = generate::generate(solver, seed, size)?;
+
+        for (part, reference) in references(solver) {
+            if let Some((solution, reference)) = disagreement(solver, part, reference, &input) {
+                return Some(Mismatch {
+                    seed,
+                    size,
+                    part,
+                    input,
+                    solution,
+                    reference,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Shrinks the input of the mismatch as long as the part disagrees in the same way, i.e. with the
+/// same side failing or both giving different answers. The input is first generated again with
+/// smaller sizes, then paragraphs and lines are removed from it.
+pub fn shrink(solver: &Solver, mut mismatch: Mismatch) -> Mismatch {
+    let reference =
+        solver.references[mismatch.part - 1].expect("part of the mismatch should have a reference");
+    let kind = |(solution, reference): &Outcomes| (solution.is_ok(), reference.is_ok());
+    let original_kind = kind(&(mismatch.solution.clone(), mismatch.reference.clone()));
+    let part = mismatch.part;
+    let still_mismatching = |input: &str| {
+        disagreement(solver, part, reference, input)
+            .filter(|outcomes| kind(outcomes) == original_kind)
+    };
+
+    for size in 1..mismatch.size {
+        let Some(input) = generate::generate(solver, mismatch.seed, size) else {
+            break;
+        };
+
+        if let Some((solution, reference)) = still_mismatching(&input) {
+            mismatch = Mismatch {
+                size,
+                input,
+                solution,
+                reference,
+                ..mismatch
+            };
+            break;
+        }
+    }
+
+    for separator in ["\n\n", "\n"] {
+        let mut units = mismatch
+            .input
+            .trim_end_matches('\n')
+            .split(separator)
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
+        let mut chunk = units.len() / 2;
+
+        // removes chunks of units, then smaller and smaller ones
+        while chunk > 0 {
+            let mut start = 0;
+
+            while start < units.len() && units.len() > 1 {
+                let end = (start + chunk).min(units.len());
+                let candidate_units = [&units[..start], &units[end..]].concat();
+                let candidate = candidate_units.join(separator) + "\n";
+
+                match still_mismatching(&candidate) {
+                    Some((solution, reference)) => {
+                        units = candidate_units;
+                        mismatch.input = candidate;
+                        mismatch.solution = solution;
+                        mismatch.reference = reference;
+                    }
+                    None => start += chunk,
+                }
+            }
+
+            chunk /= 2;
+        }
+    }
+
+    mismatch
+}
+
+/// Asserts that the parts having a reference agree with it on the inputs generated for a few seeds,
+/// showing the first mismatch shrunk. Used by the tests generated by `test_solvers!`.
+pub fn assert_references_agree(solver: &Solver) {
+    const SEEDS: Range<u64> = 0..10;
+    const SIZE: usize = 5;
+
+    if references(solver).next().is_none() {
+        println!("skipping reference solutions: none");
+        return;
+    }
+
+    if let Some(mismatch) = find_mismatch(solver, SEEDS, SIZE) {
+        panic!("{}", shrink(solver, mismatch));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_mismatch, shrink};
+    use crate::{
+        generate::{generate, Rng},
+        shared::{PuzzleResult, ReferenceSolver, Solution, SolveError, Solver},
+    };
+
+    /// Sums numbers, forgetting the negative ones.
+    struct Sum;
+
+    impl Solution for Sum {
+        type Parsed = Vec<i64>;
+
+        fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+            Ok(input.lines().map(str::parse).collect::<Result<_, _>>()?)
+        }
+
+        fn part1(numbers: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+            Ok(numbers
+                .iter()
+                .filter(|&&number| number > 0)
+                .sum::<i64>()
+                .into())
+        }
+
+        fn part2(numbers: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+            Ok(numbers.len().into())
+        }
+
+        const REFERENCES: [Option<ReferenceSolver>; 2] = [Some(reference_sum), None];
+
+        fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+            Some(
+                (0..size)
+                    .map(|_| format!("{}\n", rng.range(0..=20) as i64 - 3))
+                    .collect(),
+            )
+        }
+    }
+
+    fn reference_sum(input: &str) -> Result<PuzzleResult, SolveError> {
+        Ok(input
+            .lines()
+            .map(str::parse::<i64>)
+            .sum::<Result<i64, _>>()?
+            .into())
+    }
+
+    #[test]
+    fn first_mismatch_is_shrunk_to_a_single_line() {
+        let solver = Solver::of::<Sum>();
+
+        let mismatch = find_mismatch(&solver, 0..20, 30).expect("negative numbers should differ");
+        assert_eq!(mismatch.part, 1);
+
+        let shrunk = shrink(&solver, mismatch.clone());
+
+        assert_eq!(shrunk.seed, mismatch.seed);
+        assert!(shrunk.size <= mismatch.size);
+        assert_eq!(shrunk.input.lines().count(), 1, "{shrunk}");
+        assert!(shrunk.input.starts_with('-'));
+        assert_ne!(shrunk.solution, shrunk.reference);
+    }
+
+    #[test]
+    fn only_inputs_with_negative_numbers_mismatch() {
+        let solver = Solver::of::<Sum>();
+
+        for seed in 0..20 {
+            let input = generate(&solver, seed, 1).expect("sum should have a generator");
+
+            assert_eq!(
+                find_mismatch(&solver, seed..seed + 1, 1).is_some(),
+                input.starts_with('-'),
+                "{input}"
+            );
+        }
+    }
+}
@@ -0,0 +1,378 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{answers::Answers, shared};
+
+/// What could be extracted from a saved puzzle page.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PuzzlePage {
+    /// Text of every `<pre><code>` block of the page, in page order. The first one is usually the
+    /// example input.
+    pub code_blocks: Vec<String>,
+    /// Expected answer for the example of each part: the last emphasized code of the part's
+    /// description, as in "the total is <code><em>24000</em></code>".
+    pub answers: [Option<String>; 2],
+    /// Title of the puzzle, from the `--- Day 1: Calorie Counting ---` heading.
+    pub title: Option<String>,
+}
+
+impl PuzzlePage {
+    /// Parses the HTML of a puzzle page, as saved from the browser after solving one or both parts.
+    pub fn parse(html: &str) -> Self {
+        let code_blocks = elements(html, "<pre><code>", "</code></pre>")
+            .map(text_content)
+            .collect();
+
+        let mut answers = [None, None];
+        let descriptions = elements(html, "<article class=\"day-desc\">", "</article>");
+
+        for (answer, description) in answers.iter_mut().zip(descriptions) {
+            *answer = emphasized_code(description).pop().map(text_content);
+        }
+
+        let title = elements(html, "<h2>", "</h2>")
+            .map(text_content)
+            .find_map(|heading| {
+                let (_, title) = heading.trim().trim_matches('-').split_once(':')?;
+                Some(title.trim().to_owned())
+            })
+            .filter(|title| !title.is_empty());
+
+        Self {
+            code_blocks,
+            answers,
+            title,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ExtractError {
+    /// The page has no `<pre><code>` block, or fewer than the requested one.
+    MissingBlockError(usize, usize),
+    /// The file would overwrite an extracted file with content, which is only done when forced.
+    ExistingExampleError(PathBuf),
+    IoError(PathBuf, io::Error),
+}
+
+impl Display for ExtractError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingBlockError(block, 0) => {
+                write!(
+                    f,
+                    "expected a <pre><code> block #{block}, the page has none"
+                )
+            }
+            Self::MissingBlockError(block, blocks) => write!(
+                f,
+                "expected a <pre><code> block #{block}, the page has {blocks} of them"
+            ),
+            Self::ExistingExampleError(path) => write!(
+                f,
+                "{} already has content, use --force to overwrite it",
+                path.display()
+            ),
+            Self::IoError(path, err) => write!(f, "could not write {}: {err}", path.display()),
+        }
+    }
+}
+
+/// Path of the puzzle title of a day, shown by the `report` command.
+pub fn title_path(day_dir: &Path) -> PathBuf {
+    day_dir.join("title.txt")
+}
+
+/// Writes the 1-based `block` of the page as `test.txt` in the day's inputs folder, the
+/// emphasized answers as its `test.answers.txt` sidecar and the title as `title.txt`. Files with
+/// content are kept unless `force` is set, in which case a sidecar left without answers is removed
+/// so that the new example is not checked against the answers of the previous one. Returns the
+/// written files.
+pub fn write_example(
+    page: &PuzzlePage,
+    block: usize,
+    day_dir: &Path,
+    force: bool,
+) -> Result<Vec<PathBuf>, ExtractError> {
+    let example = block
+        .checked_sub(1)
+        .and_then(|index| page.code_blocks.get(index))
+        .ok_or(ExtractError::MissingBlockError(
+            block,
+            page.code_blocks.len(),
+        ))?;
+
+    let example_path = shared::input_path(day_dir, "test");
+    let answers_path = Answers::path(day_dir, "test");
+    let title_path = page.title.as_ref().map(|_| title_path(day_dir));
+    let mut written = vec![];
+
+    for path in [
+        Some(&example_path),
+        Some(&answers_path),
+        title_path.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if !force && fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0) {
+            return Err(ExtractError::ExistingExampleError(path.clone()));
+        }
+    }
+
+    fs::create_dir_all(day_dir).map_err(|err| ExtractError::IoError(day_dir.to_owned(), err))?;
+
+    fs::write(&example_path, example)
+        .map_err(|err| ExtractError::IoError(example_path.clone(), err))?;
+    written.push(example_path);
+
+    if page.answers.iter().any(Option::is_some) {
+        let mut answers = Answers::default();
+        for (part, answer) in (1..).zip(&page.answers) {
+            if let Some(answer) = answer {
+                answers.record(part, &answer.clone().into());
+            }
+        }
+
+        answers
+            .save(day_dir, "test")
+            .map_err(|err| ExtractError::IoError(answers_path.clone(), err))?;
+        written.push(answers_path);
+    } else {
+        match fs::remove_file(&answers_path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => {
+                return Err(ExtractError::IoError(answers_path, err));
+            }
+            _ => {}
+        }
+    }
+
+    if let (Some(title), Some(title_path)) = (&page.title, title_path) {
+        fs::write(&title_path, format!("{title}\n"))
+            .map_err(|err| ExtractError::IoError(title_path.clone(), err))?;
+        written.push(title_path);
+    }
+
+    Ok(written)
+}
+
+/// Inner HTML of every element starting with `start` and ending with `end`. Elements are not
+/// nested in the puzzle pages, so the first `end` closes the element.
+fn elements<'a>(html: &'a str, start: &'a str, end: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    let mut rest = html;
+
+    std::iter::from_fn(move || {
+        let (_, after_start) = rest.split_once(start)?;
+        let (inner, after_end) = after_start.split_once(end)?;
+        rest = after_end;
+
+        Some(inner)
+    })
+}
+
+/// Text of the `<code><em>…</em></code>` and `<em><code>…</code></em>` of a description, in order.
+fn emphasized_code(description: &str) -> Vec<&str> {
+    let mut emphasized = elements(description, "<code><em>", "</em></code>")
+        .chain(elements(description, "<em><code>", "</code></em>"))
+        .map(|inner| {
+            let offset = inner.as_ptr() as usize - description.as_ptr() as usize;
+            (offset, inner)
+        })
+        .collect::<Vec<_>>();
+
+    emphasized.sort_by_key(|(offset, _)| *offset);
+
+    emphasized.into_iter().map(|(_, inner)| inner).collect()
+}
+
+/// Text of an HTML fragment: tags are dropped and entities decoded.
+fn text_content(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    decode_entities(&text)
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(index) = rest.find('&') {
+        decoded.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        let entity = rest
+            .find(';')
+            .map(|end| (&rest[1..end], end))
+            .and_then(|(name, end)| Some((decode_entity(name)?, end)));
+
+        match entity {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    match name {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        _ => {
+            let code = match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => name.strip_prefix('#')?.parse().ok()?,
+            };
+
+            char::from_u32(code)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{write_example, ExtractError, PuzzlePage};
+    use crate::testing::TempDir;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2>
+<p>For example:</p>
+<pre><code>1000
+2000
+
+<em>4000</em>
+</code></pre>
+<p>In the example above, this is <em>4000</em> (<code>a &lt; b</code>).</p>
+<p>Find the Elf carrying the most Calories. That is <code><em>24000</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>69206</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>x &amp;&amp; y</code></pre>
+<p>The sum is <em><code>45000</code></em>, not <code><em>4000</em></code> later on.</p>
+<p>Wait, it is <code><em>45000</em></code>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn page_yields_code_blocks_and_emphasized_answers() {
+        let page = PuzzlePage::parse(PAGE);
+
+        assert_eq!(
+            page.code_blocks,
+            ["1000\n2000\n\n4000\n", "x && y"].map(str::to_owned)
+        );
+        assert_eq!(
+            page.answers,
+            [Some("24000".to_owned()), Some("45000".to_owned())]
+        );
+        assert_eq!(page.title.as_deref(), Some("Calorie Counting"));
+    }
+
+    #[test]
+    fn page_solved_once_only_has_the_first_answer() {
+        let (first_part, _) = PAGE.split_once("<p>Your puzzle answer").unwrap();
+
+        let page = PuzzlePage::parse(first_part);
+
+        assert_eq!(page.answers, [Some("24000".to_owned()), None]);
+    }
+
+    #[test]
+    fn example_and_sidecar_are_written_without_overwriting() {
+        let temp_dir = TempDir::new("extract");
+        let day_dir = temp_dir.path();
+        let page = PuzzlePage::parse(PAGE);
+
+        let written = write_example(&page, 1, day_dir, false).unwrap();
+
+        assert_eq!(
+            written,
+            [
+                day_dir.join("test.txt"),
+                day_dir.join("test.answers.txt"),
+                day_dir.join("title.txt")
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(day_dir.join("test.txt")).unwrap(),
+            "1000\n2000\n\n4000\n"
+        );
+        assert_eq!(
+            fs::read_to_string(day_dir.join("test.answers.txt")).unwrap(),
+            "part1: 24000\npart2: 45000\n"
+        );
+        assert_eq!(
+            fs::read_to_string(day_dir.join("title.txt")).unwrap(),
+            "Calorie Counting\n"
+        );
+
+        assert!(matches!(
+            write_example(&page, 2, day_dir, false),
+            Err(ExtractError::ExistingExampleError(_))
+        ));
+        assert!(matches!(
+            write_example(&page, 3, day_dir, true),
+            Err(ExtractError::MissingBlockError(3, 2))
+        ));
+
+        write_example(&page, 2, day_dir, true).unwrap();
+        assert_eq!(
+            fs::read_to_string(day_dir.join("test.txt")).unwrap(),
+            "x && y"
+        );
+    }
+
+    #[test]
+    fn forced_extraction_leaves_no_stale_answers() {
+        let temp_dir = TempDir::new("extract-force");
+        let day_dir = temp_dir.path();
+        write_example(&PuzzlePage::parse(PAGE), 1, day_dir, false).unwrap();
+
+        // the title is kept like the example and its answers
+        fs::write(day_dir.join("test.txt"), "").unwrap();
+        fs::remove_file(day_dir.join("test.answers.txt")).unwrap();
+        assert!(matches!(
+            write_example(&PuzzlePage::parse(PAGE), 1, day_dir, false),
+            Err(ExtractError::ExistingExampleError(path)) if path == day_dir.join("title.txt")
+        ));
+
+        write_example(&PuzzlePage::parse(PAGE), 1, day_dir, true).unwrap();
+        let unsolved = PuzzlePage {
+            code_blocks: vec!["5\n".to_owned()],
+            ..PuzzlePage::default()
+        };
+        let written = write_example(&unsolved, 1, day_dir, true).unwrap();
+
+        assert_eq!(written, [day_dir.join("test.txt")]);
+        assert!(!day_dir.join("test.answers.txt").exists());
+        assert_eq!(
+            fs::read_to_string(day_dir.join("title.txt")).unwrap(),
+            "Calorie Counting\n"
+        );
+    }
+}
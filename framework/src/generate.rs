@@ -0,0 +1,112 @@
+use std::ops::RangeInclusive;
+
+use crate::shared::Solver;
+
+/// Small pseudo-random generator (SplitMix64) for puzzle input generators. The same seed gives
+/// the same numbers on every platform, so that a generated input can be reproduced from its seed.
+/// Not suitable for anything but test data.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in the range, bounds included.
+    pub fn range(&mut self, range: RangeInclusive<usize>) -> usize {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "range should not be empty");
+
+        match (end - start).checked_add(1) {
+            Some(len) => start + (self.next_u64() % len as u64) as usize,
+            None => self.next_u64() as usize,
+        }
+    }
+
+    /// Whether an event with the given chance happened, e.g. `ratio(1, 4)` is true a quarter of
+    /// the time.
+    pub fn ratio(&mut self, numerator: usize, denominator: usize) -> bool {
+        self.range(1..=denominator) <= numerator
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..=items.len() - 1)]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.range(0..=index));
+        }
+    }
+}
+
+/// Input of the solver's generator for the seed and size, `None` when the day has no generator.
+pub fn generate(solver: &Solver, seed: u64, size: usize) -> Option<String> {
+    (solver.generate)(&mut Rng::new(seed), size)
+}
+
+/// Asserts that the inputs generated for a few seeds are the same for the same seed and that both
+/// parts solve them. Used by the tests generated by `test_solvers!`.
+pub fn assert_generated_inputs_are_solved(solver: &Solver) {
+    const SIZE: usize = 20;
+
+    for seed in 0..5 {
+        let Some(input) = generate(solver, seed, SIZE) else {
+            println!("skipping generated inputs: no generator");
+            return;
+        };
+        assert_eq!(
+            generate(solver, seed, SIZE).as_deref(),
+            Some(input.as_str()),
+            "input generated for seed {seed} should not change"
+        );
+
+        let parsed = (solver.parse)(&input)
+            .unwrap_or_else(|err| panic!("input generated for seed {seed} should parse: {err}"));
+        for (part, solve_part) in (1..).zip(solver.parts) {
+            if let Err(err) = solve_part(&parsed) {
+                panic!("part {part} should solve the input generated for seed {seed}: {err}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn same_seed_gives_same_numbers() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.range(1..=6)).collect::<Vec<_>>()
+        };
+
+        assert_eq!(numbers(42), numbers(42));
+        assert_ne!(numbers(42), numbers(43));
+        assert!(numbers(7).iter().all(|number| (1..=6).contains(number)));
+    }
+
+    #[test]
+    fn shuffle_keeps_the_items() {
+        let mut items = (0..20).collect::<Vec<_>>();
+
+        Rng::new(1).shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
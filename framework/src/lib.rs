@@ -0,0 +1,28 @@
+//! Framework shared by the Advent of Code year crates: the [`shared::Solution`] trait and the types
+//! around it, input and answer loading, example extraction from saved puzzle pages, random input
+//! generation, differential testing against reference solutions, letter recognition, the client
+//! downloading inputs and submitting answers, the runner, benchmarks, allocation counting, watch
+//! mode, the README report and the `test_solvers!` macro.
+//!
+//! A year crate implements [`shared::Solution`] for each of its days and exports an
+//! [`shared::Edition`] listing their solvers.
+
+pub mod answers;
+pub mod bench;
+pub mod bench_history;
+pub mod client;
+pub mod diagnostic;
+pub mod differential;
+pub mod extract;
+pub mod generate;
+pub mod memory;
+pub mod ocr;
+pub mod output;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod shared;
+pub mod submit;
+#[cfg(test)]
+mod testing;
+pub mod watch;
@@ -0,0 +1,195 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Global allocator counting the allocations of each thread on top of the system allocator.
+///
+/// Binaries opt in by installing it, after which [`measure`] reports what a solver allocated:
+///
+/// ```no_run
+/// use aoc_framework::memory::CountingAllocator;
+///
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+/// # fn main() {}
+/// ```
+pub struct CountingAllocator;
+
+/// Set by the first allocation going through [`CountingAllocator`], telling that counters are live.
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+struct Counters {
+    allocations: Cell<u64>,
+    bytes: Cell<u64>,
+    /// Bytes allocated and not freed yet by the thread. Memory freed by another thread than the one
+    /// that allocated it can make it negative.
+    live_bytes: Cell<i64>,
+    peak_live_bytes: Cell<i64>,
+}
+
+thread_local! {
+    // const-initialized without a destructor, so that using it never allocates
+    static COUNTERS: Counters = const {
+        Counters {
+            allocations: Cell::new(0),
+            bytes: Cell::new(0),
+            live_bytes: Cell::new(0),
+            peak_live_bytes: Cell::new(0),
+        }
+    };
+}
+
+fn record_allocation(size: usize) {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        INSTALLED.store(true, Ordering::Relaxed);
+    }
+
+    let _ = COUNTERS.try_with(|counters| {
+        counters.allocations.set(counters.allocations.get() + 1);
+        counters.bytes.set(counters.bytes.get() + size as u64);
+        record_live_change(counters, size as i64);
+    });
+}
+
+fn record_free(size: usize) {
+    let _ = COUNTERS.try_with(|counters| record_live_change(counters, -(size as i64)));
+}
+
+fn record_live_change(counters: &Counters, change: i64) {
+    let live_bytes = counters.live_bytes.get() + change;
+
+    counters.live_bytes.set(live_bytes);
+    counters
+        .peak_live_bytes
+        .set(counters.peak_live_bytes.get().max(live_bytes));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_free(layout.size());
+    }
+
+    /// Counts as a new allocation of `new_size` bytes replacing the previous one, as growing a
+    /// `Vec` does.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_free(layout.size());
+            record_allocation(new_size);
+        }
+
+        new_ptr
+    }
+}
+
+/// What a piece of code allocated on its thread.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64,
+    /// Highest amount of memory allocated and not freed yet at any point, above what was live
+    /// before.
+    pub peak_live_bytes: u64,
+}
+
+/// Calls `f`, counting its allocations on the current thread. The stats are `None` when the
+/// binary did not install [`CountingAllocator`].
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    let start = COUNTERS.with(|counters| {
+        counters.peak_live_bytes.set(counters.live_bytes.get());
+
+        (
+            counters.allocations.get(),
+            counters.bytes.get(),
+            counters.live_bytes.get(),
+        )
+    });
+
+    let value = f();
+
+    let stats = COUNTERS.with(|counters| {
+        let (allocations, bytes, live_bytes) = start;
+
+        AllocStats {
+            allocations: counters.allocations.get() - allocations,
+            bytes: counters.bytes.get() - bytes,
+            peak_live_bytes: (counters.peak_live_bytes.get() - live_bytes).max(0) as u64,
+        }
+    });
+
+    (value, INSTALLED.load(Ordering::Relaxed).then_some(stats))
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.50 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+
+    for next_unit in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next_unit;
+    }
+
+    format!("{value:.2} {unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_bytes, measure, CountingAllocator};
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn allocations_of_the_closure_are_counted() {
+        let (_, stats) = measure(|| {
+            let mut numbers = Vec::<u64>::with_capacity(100);
+            numbers.push(1);
+            drop(numbers);
+
+            vec![0_u8; 1000]
+        });
+        let stats = stats.expect("counting allocator should be installed for tests");
+
+        assert_eq!(stats.allocations, 2);
+        assert_eq!(stats.bytes, 1800);
+        assert_eq!(stats.peak_live_bytes, 1000);
+    }
+
+    #[test]
+    fn bytes_are_formatted_with_binary_units() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.00 MiB");
+    }
+}
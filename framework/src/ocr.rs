@@ -0,0 +1,252 @@
+use std::{error::Error, fmt::Display};
+
+use crate::shared::{Grid, PuzzleResult};
+
+/// A block-letter font of the puzzles. Glyphs are written row by row without their blank columns,
+/// as letters are told apart by the blank columns between them.
+struct Font {
+    height: usize,
+    glyphs: &'static [(char, &'static str)],
+}
+
+/// The font of the 6 pixels high screens, with 4 pixels wide letters (Y is 5 pixels wide).
+const SMALL_FONT: Font = Font {
+    height: 6,
+    glyphs: &[
+        ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+        ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+        ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+        ('E', "####\n#...\n###.\n#...\n#...\n####"),
+        ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+        ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+        ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+        ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+        ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+        ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+        ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+        ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+        ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+        ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+        ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+        ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+        ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+        ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+    ],
+};
+
+/// The font of the 10 pixels high messages, with 6 pixels wide letters.
+const LARGE_FONT: Font = Font {
+    height: 10,
+    glyphs: &[
+        (
+            'A',
+            "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+        ),
+        (
+            'B',
+            "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+        ),
+        (
+            'C',
+            ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+        ),
+        (
+            'E',
+            "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+        ),
+        (
+            'F',
+            "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+        ),
+        (
+            'G',
+            ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+        ),
+        (
+            'H',
+            "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+        ),
+        (
+            'J',
+            "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+        ),
+        (
+            'K',
+            "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+        ),
+        (
+            'L',
+            "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+        ),
+        (
+            'N',
+            "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+        ),
+        (
+            'P',
+            "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+        ),
+        (
+            'R',
+            "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+        ),
+        (
+            'X',
+            "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+        ),
+        (
+            'Z',
+            "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+        ),
+    ],
+};
+
+const FONTS: [Font; 2] = [SMALL_FONT, LARGE_FONT];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum OcrError {
+    /// No font is as high as the grid.
+    UnsupportedHeightError(usize),
+    /// The glyph starting at the column is not a letter of the font, along with its drawing.
+    UnknownGlyphError(usize, String),
+    NoLetterError,
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedHeightError(height) => write!(
+                f,
+                "letters should be {} or {} pixels high, got a grid of {height} rows",
+                SMALL_FONT.height, LARGE_FONT.height
+            ),
+            Self::UnknownGlyphError(column, drawing) => {
+                write!(f, "unknown glyph at column {column}:\n{drawing}")
+            }
+            Self::NoLetterError => write!(f, "grid should have lit pixels"),
+        }
+    }
+}
+
+impl Error for OcrError {}
+
+/// Reads the letters drawn on the grid, in the font matching its height.
+pub fn recognize(grid: &Grid) -> Result<String, OcrError> {
+    let font = FONTS
+        .iter()
+        .find(|font| font.height == grid.height())
+        .ok_or(OcrError::UnsupportedHeightError(grid.height()))?;
+
+    let is_blank = |column: usize| (0..grid.height()).all(|row| !grid.get(column, row));
+    let mut letters = String::new();
+    let mut column = 0;
+
+    while column < grid.width() {
+        if is_blank(column) {
+            column += 1;
+            continue;
+        }
+
+        let start = column;
+        while column < grid.width() && !is_blank(column) {
+            column += 1;
+        }
+
+        let drawing = (0..grid.height())
+            .map(|row| {
+                (start..column)
+                    .map(|column| if grid.get(column, row) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let (letter, _) = font
+            .glyphs
+            .iter()
+            .find(|(_, glyph)| *glyph == drawing)
+            .ok_or(OcrError::UnknownGlyphError(start, drawing))?;
+
+        letters.push(*letter);
+    }
+
+    if letters.is_empty() {
+        return Err(OcrError::NoLetterError);
+    }
+
+    Ok(letters)
+}
+
+/// The letters drawn on the grid as a text answer, or the grid itself when they cannot be read,
+/// e.g. for the patterns drawn by examples.
+pub fn to_answer(grid: Grid) -> PuzzleResult {
+    match recognize(&grid) {
+        Ok(letters) => letters.into(),
+        Err(_) => grid.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{recognize, to_answer, OcrError};
+    use crate::shared::{Grid, PuzzleResult};
+
+    #[test]
+    fn small_letters_are_recognized() {
+        let grid = Grid::from_str(
+            "
+####...##.#..#.###..#..#.#....###..####.
+#.......#.#..#.#..#.#..#.#....#..#....#.
+###.....#.#..#.###..#..#.#....#..#...#..
+#.......#.#..#.#..#.#..#.#....###...#...
+#....#..#.#..#.#..#.#..#.#....#.#..#....
+#.....##...##..###...##..####.#..#.####.
+",
+        )
+        .unwrap();
+
+        assert_eq!(recognize(&grid), Ok("FJUBULRZ".to_owned()));
+    }
+
+    #[test]
+    fn large_letters_are_recognized() {
+        let grid = Grid::from_str(
+            "
+#....#..######
+#....#.......#
+.#..#........#
+.#..#.......#.
+..##.......#..
+..##......#...
+.#..#....#....
+.#..#...#.....
+#....#..#.....
+#....#..######
+",
+        )
+        .unwrap();
+
+        assert_eq!(recognize(&grid), Ok("XZ".to_owned()));
+    }
+
+    #[test]
+    fn unknown_glyphs_keep_the_grid() {
+        let grid = Grid::from_str("##..##\n##..##\n##..##\n##..##\n##..##\n##..##").unwrap();
+
+        assert_eq!(
+            recognize(&grid),
+            Err(OcrError::UnknownGlyphError(
+                0,
+                "##\n##\n##\n##\n##\n##".to_owned()
+            ))
+        );
+        assert_eq!(to_answer(grid.clone()), PuzzleResult::Grid(grid));
+        assert_eq!(
+            recognize(&Grid::new(4, 7)),
+            Err(OcrError::UnsupportedHeightError(7))
+        );
+        assert_eq!(recognize(&Grid::new(4, 6)), Err(OcrError::NoLetterError));
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt::Write;
+
+use crate::{
+    answers::{Answers, Verdict},
+    runner::SolverRun,
+    shared::PuzzleResult,
+};
+
+/// Renders the runs as a JSON array with one object per solver run.
+///
+/// Answers keep their `PuzzleResult` type: numbers are JSON numbers, while text and grids are JSON
+/// strings.
+/// `answer` is `null` when the solver failed, in which case `error` holds the reason. `parse_ns` is
+/// only set on the first run of each day, as both parts share the parsed input.
+pub fn render_json(runs: &[SolverRun], data_set: &str) -> String {
+    let records = runs
+        .iter()
+        .map(|run| {
+            let answer = match &run.outcome {
+                Ok(result @ (PuzzleResult::Text(_) | PuzzleResult::Grid(_))) => {
+                    json_string(&result.to_string())
+                }
+                Ok(result) => result.to_string(),
+                Err(_) => "null".to_owned(),
+            };
+            let error = match &run.outcome {
+                Ok(_) => "null".to_owned(),
+                Err(message) => json_string(message),
+            };
+            let verdict = run
+                .verdict
+                .as_ref()
+                .map(|verdict| json_string(verdict_name(verdict)))
+                .unwrap_or_else(|| "null".to_owned());
+            let parse_ns = run
+                .parse_elapsed
+                .map(|elapsed| elapsed.as_nanos().to_string())
+                .unwrap_or_else(|| "null".to_owned());
+
+            format!(
+                "  {{\"day\": {}, \"part\": {}, \"data_set\": {}, \"answer\": {answer}, \"parse_ns\": {parse_ns}, \"duration_ns\": {}, \"verdict\": {verdict}, \"error\": {error}}}",
+                run.day,
+                run.part,
+                json_string(data_set),
+                run.elapsed.as_nanos(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if records.is_empty() {
+        return "[]".to_owned();
+    }
+
+    format!("[\n{}\n]", records.join(",\n"))
+}
+
+/// Renders the runs as CSV with a header row. As CSV is untyped, `answer_type` tells whether the
+/// answer is a `num`, `text` or `grid`; both are empty when the solver failed.
+pub fn render_csv(runs: &[SolverRun], data_set: &str) -> String {
+    let mut csv =
+        "day,part,data_set,answer_type,answer,parse_ns,duration_ns,verdict,error\n".to_owned();
+
+    for run in runs {
+        let (answer_type, answer) = match &run.outcome {
+            Ok(result @ PuzzleResult::Text(_)) => ("text", result.to_string()),
+            Ok(result @ PuzzleResult::Grid(_)) => ("grid", result.to_string()),
+            Ok(result) => ("num", result.to_string()),
+            Err(_) => ("", String::new()),
+        };
+        let error = run.outcome.as_ref().err().map_or("", String::as_str);
+        let verdict = run.verdict.as_ref().map_or("", verdict_name);
+        let parse_ns = run
+            .parse_elapsed
+            .map(|elapsed| elapsed.as_nanos().to_string())
+            .unwrap_or_default();
+
+        let _ = writeln!(
+            csv,
+            "{},{},{},{answer_type},{},{parse_ns},{},{verdict},{}",
+            run.day,
+            run.part,
+            csv_field(data_set),
+            csv_field(&answer),
+            run.elapsed.as_nanos(),
+            csv_field(error),
+        );
+    }
+
+    csv
+}
+
+/// Renders the answers of the runs in the `answers.txt` format, leaving out failed parts.
+pub fn render_answers(runs: &[SolverRun]) -> String {
+    let mut answers = Answers::default();
+
+    for run in runs {
+        if let Ok(result) = &run.outcome {
+            answers.record(run.part, result);
+        }
+    }
+
+    answers.to_string()
+}
+
+fn verdict_name(verdict: &Verdict) -> &'static str {
+    match verdict {
+        Verdict::Correct => "correct",
+        Verdict::Wrong(_) => "wrong",
+        Verdict::Unknown => "unknown",
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+/// Quotes the field when it contains a separator, a quote or a line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{render_answers, render_csv, render_json};
+    use crate::{answers::Verdict, runner::SolverRun, shared::PuzzleResult};
+
+    fn runs() -> Vec<SolverRun> {
+        vec![
+            SolverRun {
+                day: 10,
+                part: 1,
+                outcome: Ok(PuzzleResult::from(13140)),
+                elapsed: Duration::from_micros(12),
+                parse_elapsed: Some(Duration::from_micros(3)),
+                alloc: None,
+                verdict: Some(Verdict::Correct),
+            },
+            SolverRun {
+                day: 10,
+                part: 2,
+                outcome: Ok(PuzzleResult::from("##..\n\"#\"".to_owned())),
+                elapsed: Duration::from_nanos(1500),
+                parse_elapsed: None,
+                alloc: None,
+                verdict: None,
+            },
+            SolverRun {
+                day: 11,
+                part: 1,
+                outcome: Err("invalid input: missing line, oops".to_owned()),
+                elapsed: Duration::ZERO,
+                parse_elapsed: Some(Duration::from_nanos(250)),
+                alloc: None,
+                verdict: Some(Verdict::Unknown),
+            },
+        ]
+    }
+
+    #[test]
+    fn json_keeps_answer_types() {
+        assert_eq!(
+            render_json(&runs(), "test"),
+            r###"[
+  {"day": 10, "part": 1, "data_set": "test", "answer": 13140, "parse_ns": 3000, "duration_ns": 12000, "verdict": "correct", "error": null},
+  {"day": 10, "part": 2, "data_set": "test", "answer": "##..\n\"#\"", "parse_ns": null, "duration_ns": 1500, "verdict": null, "error": null},
+  {"day": 11, "part": 1, "data_set": "test", "answer": null, "parse_ns": 250, "duration_ns": 0, "verdict": "unknown", "error": "invalid input: missing line, oops"}
+]"###
+        );
+        assert_eq!(render_json(&[], "test"), "[]");
+    }
+
+    #[test]
+    fn csv_quotes_fields_that_need_it() {
+        assert_eq!(
+            render_csv(&runs(), "real"),
+            "day,part,data_set,answer_type,answer,parse_ns,duration_ns,verdict,error\n\
+             10,1,real,num,13140,3000,12000,correct,\n\
+             10,2,real,text,\"##..\n\"\"#\"\"\",,1500,,\n\
+             11,1,real,,,250,0,unknown,\"invalid input: missing line, oops\"\n"
+        );
+    }
+
+    #[test]
+    fn answers_leave_out_failed_parts() {
+        assert_eq!(
+            render_answers(&runs()),
+            "part1: 13140\npart2: ##..\\n\"#\"\n"
+        );
+    }
+}
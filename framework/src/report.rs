@@ -0,0 +1,335 @@
+use std::{
+    error::Error,
+    fmt::{Display, Write},
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    answers::{Answers, Verdict},
+    extract,
+    runner::AllRuns,
+    shared::{DataSet, Edition},
+    submit::{History, SubmitOutcome},
+};
+
+/// Lines around the generated section of a README. Everything between them is replaced on each
+/// report, while the rest of the file is left alone.
+pub const START_MARKER: &str = "<!-- report:start -->";
+pub const END_MARKER: &str = "<!-- report:end -->";
+
+/// How a part fared on the real input.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PartReport {
+    /// Comparison with the recorded answer, `None` when the solver failed.
+    pub verdict: Option<Verdict>,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DayReport {
+    pub day: usize,
+    pub title: Option<String>,
+    /// Number of parts solved, see [`stars`].
+    pub stars: usize,
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+#[derive(Debug)]
+pub enum ReportError {
+    IoError(PathBuf, io::Error),
+}
+
+impl Display for ReportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::IoError(path, err) => write!(f, "could not read {}: {err}", path.display()),
+        }
+    }
+}
+
+impl Error for ReportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::IoError(_, err) => Some(err),
+        }
+    }
+}
+
+/// Gathers the runs of every day on its real input with the titles, submission histories and
+/// recorded answers stored next to the inputs.
+pub fn day_reports(edition: &Edition, all_runs: AllRuns) -> Result<Vec<DayReport>, ReportError> {
+    let mut reports: Vec<DayReport> = vec![];
+
+    for run in all_runs.runs {
+        if reports.last().map(|report| report.day) != Some(run.day) {
+            let day_dir = edition.day_dir(run.day);
+            let title = fs::read_to_string(extract::title_path(&day_dir))
+                .ok()
+                .map(|title| title.trim().to_owned())
+                .filter(|title| !title.is_empty());
+
+            reports.push(DayReport {
+                day: run.day,
+                title,
+                stars: stars(&day_dir)?,
+                parse_elapsed: Duration::ZERO,
+                parts: vec![],
+            });
+        }
+
+        let report = reports.last_mut().expect("report of the day should exist");
+        report.parse_elapsed += run.parse_elapsed.unwrap_or_default();
+        report.parts.push(PartReport {
+            verdict: run.outcome.is_ok().then_some(run.verdict).flatten(),
+            elapsed: run.elapsed,
+        });
+    }
+
+    Ok(reports)
+}
+
+/// Number of parts solved: those the server accepted an answer for according to
+/// `submissions.txt`, or for days without any submission, those with an answer recorded in
+/// `answers.txt`.
+pub fn stars(day_dir: &Path) -> Result<usize, ReportError> {
+    let history =
+        History::load(day_dir).map_err(|err| ReportError::IoError(History::path(day_dir), err))?;
+
+    if history.attempts().is_empty() {
+        let input_name = DataSet::Real.filename();
+        let answers = Answers::load(day_dir, input_name)
+            .map_err(|err| ReportError::IoError(Answers::path(day_dir, input_name), err))?;
+
+        return Ok((1..=2).filter(|&part| answers.get(part).is_some()).count());
+    }
+
+    let stars = (1..=2)
+        .filter(|&part| {
+            history
+                .attempts()
+                .iter()
+                .any(|attempt| attempt.part == part && attempt.outcome == SubmitOutcome::Correct)
+        })
+        .count();
+
+    Ok(stars)
+}
+
+/// Renders the reports as a Markdown table linking each day to its puzzle, with a legend. The
+/// times of the parts and of parsing are only shown with `timings`, as they depend on the machine
+/// and change with every run.
+pub fn render_table(year: u16, reports: &[DayReport], timings: bool) -> String {
+    let mut table = if timings {
+        "| Day | Puzzle | Stars | Part 1 | Part 2 | Parse |\n\
+         |----:|--------|:-----:|-------:|-------:|------:|\n"
+    } else {
+        "| Day | Puzzle | Stars | Part 1 | Part 2 |\n\
+         |----:|--------|:-----:|:------:|:------:|\n"
+    }
+    .to_owned();
+
+    for report in reports {
+        let title = report
+            .title
+            .clone()
+            .unwrap_or_else(|| format!("Day {}", report.day));
+        let stars = "★".repeat(report.stars) + &"☆".repeat(2 - report.stars.min(2));
+        let parts = report
+            .parts
+            .iter()
+            .map(|part| match &part.verdict {
+                Some(verdict) if timings => format!("{verdict} {:.2?}", part.elapsed),
+                Some(verdict) => verdict.to_string(),
+                None => "failed".to_owned(),
+            })
+            .collect::<Vec<_>>();
+
+        let _ = write!(
+            table,
+            "| {} | [{}](https://adventofcode.com/{year}/day/{}) | {stars} | {} | {} |",
+            report.day,
+            title.replace('|', "\\|"),
+            report.day,
+            parts.first().map_or("", String::as_str),
+            parts.get(1).map_or("", String::as_str),
+        );
+        if timings {
+            let _ = write!(table, " {:.2?} |", report.parse_elapsed);
+        }
+        table.push('\n');
+    }
+
+    table.push_str(
+        "\n★ part solved: answer accepted when submitted, or recorded for days without submissions. \
+         ✓ matches the recorded answer, ✗ differs from it, ? no recorded answer yet.",
+    );
+    if timings {
+        table.push_str(" Times are a local snapshot of a single run on the real input.");
+    }
+    table.push('\n');
+
+    table
+}
+
+/// Replaces the section between the markers with `table`. Content without markers gets a new
+/// section at its end.
+pub fn replace_section(content: &str, table: &str) -> String {
+    let section = format!("{START_MARKER}\n{table}{END_MARKER}");
+
+    let markers = content.find(START_MARKER).and_then(|start| {
+        let end = start + content[start..].find(END_MARKER)?;
+        Some((start, end + END_MARKER.len()))
+    });
+
+    match markers {
+        Some((start, end)) => format!("{}{section}{}", &content[..start], &content[end..]),
+        None if content.trim().is_empty() => format!("{section}\n"),
+        None => format!("{}\n\n## Results\n\n{section}\n", content.trim_end()),
+    }
+}
+
+/// Writes the table into the README of the year's crate, creating it when missing.
+pub fn write_readme(edition: &Edition, table: &str) -> io::Result<PathBuf> {
+    let path = Path::new(edition.root).join("README.md");
+
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            format!("# Advent of Code {}\n", edition.year)
+        }
+        Err(err) => return Err(err),
+    };
+
+    fs::write(&path, replace_section(&content, table))?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, time::Duration};
+
+    use super::{render_table, replace_section, stars, DayReport, PartReport, ReportError};
+    use crate::{answers::Verdict, testing::TempDir};
+
+    #[test]
+    fn table_shows_stars_verdicts_and_timings() {
+        let reports = [
+            DayReport {
+                day: 1,
+                title: Some("Calorie Counting".to_owned()),
+                stars: 2,
+                parse_elapsed: Duration::from_micros(900),
+                parts: vec![
+                    PartReport {
+                        verdict: Some(Verdict::Correct),
+                        elapsed: Duration::from_micros(45),
+                    },
+                    PartReport {
+                        verdict: Some(Verdict::Wrong("197400".to_owned())),
+                        elapsed: Duration::from_micros(62),
+                    },
+                ],
+            },
+            DayReport {
+                day: 2,
+                title: None,
+                stars: 0,
+                parse_elapsed: Duration::ZERO,
+                parts: vec![
+                    PartReport {
+                        verdict: None,
+                        elapsed: Duration::ZERO,
+                    },
+                    PartReport {
+                        verdict: Some(Verdict::Unknown),
+                        elapsed: Duration::from_millis(3),
+                    },
+                ],
+            },
+        ];
+
+        let table = render_table(2022, &reports, true);
+        let rows = table.lines().collect::<Vec<_>>();
+
+        assert_eq!(
+            rows[2],
+            "| 1 | [Calorie Counting](https://adventofcode.com/2022/day/1) | ★★ | ✓ 45.00µs | ✗ 62.00µs | 900.00µs |"
+        );
+        assert_eq!(
+            rows[3],
+            "| 2 | [Day 2](https://adventofcode.com/2022/day/2) | ☆☆ | failed | ? 3.00ms | 0.00ns |"
+        );
+    }
+
+    #[test]
+    fn table_leaves_timings_out_unless_asked() {
+        let report = DayReport {
+            day: 1,
+            title: Some("Calorie Counting".to_owned()),
+            stars: 1,
+            parse_elapsed: Duration::from_micros(900),
+            parts: vec![
+                PartReport {
+                    verdict: Some(Verdict::Correct),
+                    elapsed: Duration::from_micros(45),
+                },
+                PartReport {
+                    verdict: None,
+                    elapsed: Duration::ZERO,
+                },
+            ],
+        };
+
+        let table = render_table(2022, &[report], false);
+        let rows = table.lines().collect::<Vec<_>>();
+
+        assert_eq!(rows[0], "| Day | Puzzle | Stars | Part 1 | Part 2 |");
+        assert_eq!(
+            rows[2],
+            "| 1 | [Calorie Counting](https://adventofcode.com/2022/day/1) | ★☆ | ✓ | failed |"
+        );
+        assert!(!table.contains("Times"));
+    }
+
+    #[test]
+    fn stars_come_from_submissions_then_recorded_answers() {
+        let temp_dir = TempDir::new("report-stars");
+        let day_dir = temp_dir.path();
+
+        assert_eq!(stars(day_dir).unwrap(), 0);
+
+        fs::write(day_dir.join("answers.txt"), "part1: 24000\npart2: 45000\n").unwrap();
+        assert_eq!(stars(day_dir).unwrap(), 2);
+
+        fs::write(
+            day_dir.join("submissions.txt"),
+            "part1\t1700000000\twrong\t1\npart1\t1700000100\tcorrect\t24000\n",
+        )
+        .unwrap();
+        assert_eq!(stars(day_dir).unwrap(), 1);
+
+        fs::write(day_dir.join("submissions.txt"), "garbage\n").unwrap();
+        assert!(matches!(
+            stars(day_dir),
+            Err(ReportError::IoError(path, _)) if path == day_dir.join("submissions.txt")
+        ));
+    }
+
+    #[test]
+    fn section_is_replaced_between_markers_or_appended() {
+        let content = "# 2022\n\n<!-- report:start -->\nold\n<!-- report:end -->\n\nNotes\n";
+
+        assert_eq!(
+            replace_section(content, "new\n"),
+            "# 2022\n\n<!-- report:start -->\nnew\n<!-- report:end -->\n\nNotes\n"
+        );
+        assert_eq!(
+            replace_section("# 2022\n", "new\n"),
+            "# 2022\n\n## Results\n\n<!-- report:start -->\nnew\n<!-- report:end -->\n"
+        );
+    }
+}
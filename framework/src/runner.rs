@@ -0,0 +1,552 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Once,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    answers::{Answers, Verdict},
+    memory::{self, AllocStats},
+    shared::{
+        self, DataSet, Edition, ParsedInput, PuzzleResult, ReferenceSolver, SolveError, Solver,
+    },
+};
+
+pub struct SolverRun {
+    pub day: usize,
+    pub part: usize,
+    pub outcome: Result<PuzzleResult, String>,
+    pub elapsed: Duration,
+    /// Time spent parsing the input, set on the first run of the day only as the parsed input is
+    /// shared by its parts.
+    pub parse_elapsed: Option<Duration>,
+    /// What the part allocated, when the binary counts allocations.
+    pub alloc: Option<AllocStats>,
+    /// Comparison with the recorded or expected answer for the input.
+    pub verdict: Option<Verdict>,
+}
+
+impl SolverRun {
+    pub fn is_failed(&self) -> bool {
+        self.outcome.is_err() || matches!(self.verdict, Some(Verdict::Wrong(_)))
+    }
+}
+
+/// Parses the input for the solver, turning its error or a panic inside it into an error message.
+pub fn run_parse(solver: &Solver, input: &str) -> (Result<ParsedInput, String>, Duration) {
+    let (outcome, elapsed, _) = run_caught(|| (solver.parse)(input));
+
+    (outcome, elapsed)
+}
+
+/// Solves one part of the parsed input, turning its error or a panic inside it into an error
+/// message. Allocations are counted when the binary installed the counting allocator.
+pub fn run_part(
+    solver: &Solver,
+    part: usize,
+    parsed: &ParsedInput,
+) -> (Result<PuzzleResult, String>, Duration, Option<AllocStats>) {
+    run_caught(|| (solver.parts[part - 1])(parsed))
+}
+
+/// Runs a reference solution of a part, turning its error or a panic inside it into an error
+/// message.
+pub fn run_reference(reference: ReferenceSolver, input: &str) -> Result<PuzzleResult, String> {
+    let (outcome, _, _) = run_caught(|| reference(input));
+
+    outcome
+}
+
+fn run_caught<T>(
+    f: impl FnOnce() -> Result<T, SolveError>,
+) -> (Result<T, String>, Duration, Option<AllocStats>) {
+    let start = Instant::now();
+    let (outcome, alloc) = memory::measure(|| panic::catch_unwind(AssertUnwindSafe(f)));
+    let elapsed = start.elapsed();
+
+    let outcome = match outcome {
+        Ok(result) => result.map_err(|err| err.to_string()),
+        Err(payload) => Err(panic_message(payload)),
+    };
+
+    (outcome, elapsed, alloc)
+}
+
+/// Parses the input once and runs the given parts of the day on it. When parsing fails, every
+/// part fails with the parse error.
+pub fn run_parts(day: usize, solver: &Solver, input: &str, parts: &[usize]) -> Vec<SolverRun> {
+    let (parsed, parse_elapsed) = run_parse(solver, input);
+    let mut parse_elapsed = Some(parse_elapsed);
+
+    parts
+        .iter()
+        .map(|&part| {
+            let (outcome, elapsed, alloc) = match &parsed {
+                Ok(parsed) => run_part(solver, part, parsed),
+                Err(err) => (Err(err.clone()), Duration::ZERO, None),
+            };
+
+            SolverRun {
+                day,
+                part,
+                outcome,
+                elapsed,
+                parse_elapsed: parse_elapsed.take(),
+                alloc,
+                verdict: None,
+            }
+        })
+        .collect()
+}
+
+/// Checks the result against the recorded answers for the real input, recording it first when asked.
+pub fn check_answer(
+    answers: &mut Answers,
+    part: usize,
+    outcome: &Result<PuzzleResult, String>,
+    record: bool,
+) -> Verdict {
+    match outcome {
+        Ok(result) => {
+            if record {
+                answers.record(part, result);
+            }
+            answers.check(part, result)
+        }
+        Err(_) => Verdict::Unknown,
+    }
+}
+
+/// Runs of every solver for one data set, with the wall-clock time they took altogether.
+pub struct AllRuns {
+    pub runs: Vec<SolverRun>,
+    pub wall_time: Duration,
+    pub jobs: usize,
+}
+
+/// Parses the input of every registered day, then runs both parts of every day on `jobs` threads,
+/// carrying on when a solver fails or an input is missing. Runs are returned in day and part
+/// order whatever the number of threads.
+///
+/// Results are checked against the answers for the input, and with `record` stored into them.
+pub fn run_all(edition: &Edition, data_set: DataSet, record: bool, jobs: usize) -> AllRuns {
+    let solvers = edition.solvers;
+    let days = (1..=solvers.len())
+        .map(|day| {
+            let day_dir = edition.day_dir(day);
+            let input = shared::read_input(&day_dir, data_set.filename());
+            let answers = Answers::load(&day_dir, data_set.filename())
+                .map_err(|err| format!("could not load answers: {err}"));
+
+            (day_dir, input, answers)
+        })
+        .collect::<Vec<_>>();
+
+    let parse_jobs = solvers
+        .iter()
+        .zip(&days)
+        .map(|(solver, (_, input, answers))| {
+            let input = input
+                .as_deref()
+                .map_err(|err| format!("could not read input: {err}"));
+            // a day whose answers cannot be read fails, rather than passing as unchecked
+            let input = answers.as_ref().map_err(Clone::clone).and(input);
+
+            (solver, input)
+        })
+        .collect::<Vec<_>>();
+
+    let start = Instant::now();
+    // the panic messages are reported in the summary
+    let (parsed_inputs, outcomes) = silence_panics(|| {
+        let parsed_inputs = run_jobs(&parse_jobs, jobs, |(solver, input)| match input {
+            Ok(input) => run_parse(solver, input),
+            Err(err) => (Err(err.clone()), Duration::ZERO),
+        });
+
+        let part_jobs = solvers
+            .iter()
+            .zip(&parsed_inputs)
+            .flat_map(|(solver, (parsed, _))| [1, 2].map(|part| (solver, part, parsed.as_ref())))
+            .collect::<Vec<_>>();
+
+        let outcomes = run_jobs(&part_jobs, jobs, |(solver, part, parsed)| match parsed {
+            Ok(parsed) => run_part(solver, *part, parsed),
+            Err(err) => (Err((*err).clone()), Duration::ZERO, None),
+        });
+
+        (parsed_inputs, outcomes)
+    });
+    let wall_time = start.elapsed();
+
+    let mut outcomes = outcomes.into_iter();
+    let mut runs = vec![];
+
+    for (day_index, ((day_dir, _, mut answers), (_, parse_elapsed))) in
+        days.into_iter().zip(parsed_inputs).enumerate()
+    {
+        let day = day_index + 1;
+        let mut parse_elapsed = Some(parse_elapsed);
+
+        for part in [1, 2] {
+            let (outcome, elapsed, alloc) = outcomes.next().expect("every part should have run");
+
+            let verdict = answers
+                .as_mut()
+                .ok()
+                .map(|answers| check_answer(answers, part, &outcome, record));
+
+            runs.push(SolverRun {
+                day,
+                part,
+                outcome,
+                elapsed,
+                parse_elapsed: parse_elapsed.take(),
+                alloc,
+                verdict,
+            });
+        }
+
+        if let (true, Ok(answers)) = (record, &answers) {
+            if let Err(err) = answers.save(&day_dir, data_set.filename()) {
+                eprintln!("error: could not record answers for day {day}: {err}");
+            }
+        }
+    }
+
+    AllRuns {
+        runs,
+        wall_time,
+        jobs,
+    }
+}
+
+thread_local! {
+    /// Whether panics of the thread are left to the caller of [`silence_panics`] to report.
+    static SILENCED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Calls `f` without the default panic hook, so that solver panics caught by [`run_part`] are
+/// only reported by the caller instead of also being interleaved with its output.
+///
+/// Only panics of the calling thread, and of the threads [`run_all`] starts for it, are silenced:
+/// the process-wide hook is installed once and checks a thread-local flag, so that overlapping
+/// callers neither restore each other's hook nor hide the panics of other threads.
+pub fn silence_panics<T>(f: impl FnOnce() -> T) -> T {
+    static INSTALL_HOOK: Once = Once::new();
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !SILENCED.with(Cell::get) {
+                default_hook(info);
+            }
+        }));
+    });
+
+    let was_silenced = SILENCED.with(|silenced| silenced.replace(true));
+    let result = f();
+    SILENCED.with(|silenced| silenced.set(was_silenced));
+
+    result
+}
+
+/// Runs the jobs on a pool of `threads` threads, each taking the next job not started yet, and
+/// returns their outcomes in the order of the jobs.
+fn run_jobs<J: Sync, T: Send>(jobs: &[J], threads: usize, run: impl Fn(&J) -> T + Sync) -> Vec<T> {
+    let next_job = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    // the workers run on behalf of the calling thread, silenced if it is
+    let silenced = SILENCED.with(Cell::get);
+
+    thread::scope(|scope| {
+        for _ in 0..threads.min(jobs.len()) {
+            let sender = sender.clone();
+            let next_job = &next_job;
+            let run = &run;
+
+            scope.spawn(move || {
+                SILENCED.with(|flag| flag.set(silenced));
+
+                loop {
+                    let index = next_job.fetch_add(1, Ordering::Relaxed);
+                    let Some(job) = jobs.get(index) else {
+                        break;
+                    };
+
+                    let outcome = run(job);
+
+                    // the receiver outlives the scope, so sending cannot fail
+                    let _ = sender.send((index, outcome));
+                }
+            });
+        }
+    });
+    drop(sender);
+
+    let mut outcomes = receiver.into_iter().collect::<Vec<_>>();
+    outcomes.sort_by_key(|(index, _)| *index);
+
+    outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+}
+
+/// Prints a table of the runs, followed by the wall-clock time of the whole run and the CPU time
+/// (the parse and part times summed over all threads).
+pub fn print_summary(all_runs: &AllRuns) {
+    let runs = &all_runs.runs;
+    let rows = runs
+        .iter()
+        .map(|run| {
+            let answer = match &run.outcome {
+                Ok(result) => result.to_string(),
+                Err(message) => format!("FAILED: {message}"),
+            };
+
+            (run, answer)
+        })
+        .collect::<Vec<_>>();
+
+    let answer_width = rows
+        .iter()
+        .flat_map(|(_, answer)| answer.lines())
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+
+    // allocation columns are only shown when the binary counts allocations
+    let show_alloc = runs.iter().any(|run| run.alloc.is_some());
+    let alloc_columns = |columns: [&str; 3]| {
+        if show_alloc {
+            format!(
+                " | {:>10} | {:>12} | {:>12}",
+                columns[0], columns[1], columns[2]
+            )
+        } else {
+            String::new()
+        }
+    };
+
+    println!(
+        "{:>3} | {:>4} | {:<answer_width$} | {:^5} | {:>12} | {:>12}{}",
+        "Day",
+        "Part",
+        "Answer",
+        "Check",
+        "Parse",
+        "Time",
+        alloc_columns(["Allocs", "Allocated", "Peak"])
+    );
+    println!(
+        "{:-<3}-+-{:-<4}-+-{:-<answer_width$}-+-{:-<5}-+-{:-<12}-+-{:-<12}{}",
+        "",
+        "",
+        "",
+        "",
+        "",
+        "",
+        if show_alloc {
+            format!("-+-{:-<10}-+-{:-<12}-+-{:-<12}", "", "", "")
+        } else {
+            String::new()
+        }
+    );
+
+    for (run, answer) in rows {
+        let parse_elapsed = run
+            .parse_elapsed
+            .map(|elapsed| format!("{elapsed:.2?}"))
+            .unwrap_or_default();
+        let elapsed = format!("{:.2?}", run.elapsed);
+        let check = run
+            .verdict
+            .as_ref()
+            .map(|verdict| verdict.to_string())
+            .unwrap_or_default();
+        let (allocations, bytes, peak_live_bytes) = run
+            .alloc
+            .map(|alloc| {
+                (
+                    alloc.allocations.to_string(),
+                    memory::format_bytes(alloc.bytes),
+                    memory::format_bytes(alloc.peak_live_bytes),
+                )
+            })
+            .unwrap_or_default();
+        let mut lines = answer.lines().filter(|line| !line.is_empty());
+        let first_line = lines.next().unwrap_or("");
+
+        println!(
+            "{:>3} | {:>4} | {:<answer_width$} | {:^5} | {:>12} | {:>12}{}",
+            run.day,
+            run.part,
+            first_line,
+            check,
+            parse_elapsed,
+            elapsed,
+            alloc_columns([&allocations, &bytes, &peak_live_bytes])
+        );
+
+        // multi-line answers (e.g. CRT screens) continue in the answer column
+        for line in lines {
+            println!(
+                "{:>3} | {:>4} | {:<answer_width$} | {:^5} | {:>12} | {:>12}{}",
+                "",
+                "",
+                line,
+                "",
+                "",
+                "",
+                alloc_columns(["", "", ""])
+            );
+        }
+    }
+
+    for run in runs {
+        if let Some(Verdict::Wrong(expected)) = &run.verdict {
+            println!();
+            println!("Day {} part {} expected:\n{expected}", run.day, run.part);
+        }
+    }
+
+    let failed = runs.iter().filter(|run| run.is_failed()).count();
+    let cpu_time = runs
+        .iter()
+        .map(|run| run.parse_elapsed.unwrap_or_default() + run.elapsed)
+        .sum::<Duration>();
+    let threads = match all_runs.jobs {
+        1 => "1 thread".to_owned(),
+        jobs => format!("{jobs} threads"),
+    };
+
+    println!();
+    println!(
+        "{} solvers run on {threads}, {} failed, wall time {:.2?}, CPU time {:.2?}",
+        runs.len(),
+        failed,
+        all_runs.wall_time,
+        cpu_time
+    );
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "solver panicked".to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, fs, thread, time::Duration};
+
+    use super::{run_all, run_jobs, run_parts, silence_panics, SILENCED};
+    use crate::{
+        shared::{DataSet, Edition, PuzzleResult, Solution, SolveError, Solver},
+        testing::TempDir,
+    };
+
+    #[test]
+    fn parallel_jobs_keep_their_order() {
+        let jobs = [(20, "slow"), (0, "fast"), (0, "faster")];
+
+        let outcomes = run_jobs(&jobs, 3, |&(delay, name)| {
+            thread::sleep(Duration::from_millis(delay));
+            name
+        });
+
+        assert_eq!(outcomes, ["slow", "fast", "faster"]);
+    }
+
+    #[test]
+    fn panics_are_silenced_for_the_caller_and_its_workers_only() {
+        let silenced = || SILENCED.with(Cell::get);
+
+        let (nested, after_nested, workers) = silence_panics(|| {
+            let nested = silence_panics(silenced);
+            (nested, silenced(), run_jobs(&[(), ()], 2, |_| silenced()))
+        });
+
+        assert!(nested && after_nested);
+        assert_eq!(workers, [true, true]);
+        assert!(!silenced());
+        assert!(!thread::spawn(silenced).join().unwrap());
+    }
+
+    struct Lengths;
+
+    impl Solution for Lengths {
+        type Parsed = Vec<usize>;
+
+        fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+            if input.is_empty() {
+                return Err(SolveError::invalid_input("empty input"));
+            }
+
+            Ok(input.lines().map(str::len).collect())
+        }
+
+        fn part1(lengths: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+            Ok((lengths.iter().sum::<usize>() as i64).into())
+        }
+
+        fn part2(lengths: &Self::Parsed) -> Result<PuzzleResult, SolveError> {
+            Ok((lengths.len() as i64).into())
+        }
+    }
+
+    #[test]
+    fn parts_share_the_parsed_input() {
+        let runs = run_parts(1, &Solver::of::<Lengths>(), "ab\ncde", &[1, 2]);
+
+        let outcomes = runs.iter().map(|run| &run.outcome).collect::<Vec<_>>();
+        assert_eq!(
+            outcomes,
+            [&Ok(PuzzleResult::from(5)), &Ok(PuzzleResult::from(2))]
+        );
+        assert!(runs[0].parse_elapsed.is_some());
+        assert!(runs[1].parse_elapsed.is_none());
+    }
+
+    #[test]
+    fn parse_error_fails_every_part() {
+        let runs = run_parts(1, &Solver::of::<Lengths>(), "", &[1, 2]);
+
+        assert!(runs
+            .iter()
+            .all(|run| run.outcome == Err("invalid input: empty input".to_owned())));
+    }
+
+    #[test]
+    fn malformed_answers_fail_the_day() {
+        const SOLVERS: [Solver; 1] = [Solver::of::<Lengths>()];
+
+        let root = TempDir::new("runner-test");
+        let edition = Edition {
+            year: 2022,
+            root: Box::leak(root.path().to_string_lossy().into_owned().into_boxed_str()),
+            solvers: &SOLVERS,
+        };
+        let day_dir = edition.day_dir(1);
+        fs::create_dir_all(&day_dir).unwrap();
+        fs::write(day_dir.join("input.txt"), "ab\ncde").unwrap();
+        fs::write(day_dir.join("answers.txt"), "part1 5\n").unwrap();
+
+        let all_runs = run_all(&edition, DataSet::Real, false, 1);
+
+        assert_eq!(all_runs.runs.len(), 2);
+        for run in &all_runs.runs {
+            let message = run.outcome.as_ref().unwrap_err();
+            assert!(message.starts_with("could not load answers: "), "{message}");
+            assert!(run.is_failed());
+        }
+    }
+}
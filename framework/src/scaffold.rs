@@ -0,0 +1,228 @@
+use std::{
+    fmt::{Display, Write},
+    fs::{self, OpenOptions},
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+};
+
+/// Last day of an Advent of Code edition.
+pub const LAST_DAY: usize = 25;
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// The day's solution file already exists and is left untouched.
+    DayExistsError(PathBuf),
+    /// Days are registered by position, so the previous day has to exist first.
+    MissingPreviousDayError(usize),
+    IoError(PathBuf, io::Error),
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DayExistsError(path) => write!(f, "{} already exists", path.display()),
+            Self::MissingPreviousDayError(day) => {
+                write!(f, "day {day} should be created first")
+            }
+            Self::IoError(path, err) => write!(f, "could not write {}: {err}", path.display()),
+        }
+    }
+}
+
+/// Creates day `day` in the crate at `root`: `src/day<n>.rs` rendered from `day.template`, empty
+/// `inputs/day<n>/{test,input}.txt` files and the regenerated `src/registry.rs`.
+///
+/// Existing files are never overwritten: the command fails when the solution exists, and input
+/// files already there are kept. Returns the files that were created.
+pub fn new_day(root: &Path, day: usize) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let src_dir = root.join("src");
+    let solution_path = src_dir.join(format!("day{day}.rs"));
+
+    if solution_path.exists() {
+        return Err(ScaffoldError::DayExistsError(solution_path));
+    }
+
+    let mut days = registered_days(&src_dir)?;
+    if day > 1 && !days.contains(&(day - 1)) {
+        return Err(ScaffoldError::MissingPreviousDayError(day - 1));
+    }
+
+    let template_path = root.join("day.template");
+    let template = fs::read_to_string(&template_path)
+        .map_err(|err| ScaffoldError::IoError(template_path, err))?;
+
+    let mut created = vec![];
+
+    create_new(&solution_path, &render_template(&template, day))?;
+    created.push(solution_path);
+
+    let inputs_dir = root.join("inputs").join(format!("day{day}"));
+    fs::create_dir_all(&inputs_dir)
+        .map_err(|err| ScaffoldError::IoError(inputs_dir.clone(), err))?;
+
+    for filename in ["test.txt", "input.txt"] {
+        let path = inputs_dir.join(filename);
+
+        if !path.exists() {
+            create_new(&path, "")?;
+            created.push(path);
+        }
+    }
+
+    days.push(day);
+    days.sort_unstable();
+
+    let registry_path = src_dir.join("registry.rs");
+    fs::write(&registry_path, render_registry(&days))
+        .map_err(|err| ScaffoldError::IoError(registry_path, err))?;
+
+    Ok(created)
+}
+
+/// Names the template's `DayN` solution after the day.
+pub fn render_template(template: &str, day: usize) -> String {
+    template.replace("DayN", &format!("Day{day}"))
+}
+
+/// Renders `src/registry.rs`, declaring the module of every day and listing their solvers.
+pub fn render_registry(days: &[usize]) -> String {
+    let mut registry = "\
+// Generated by the `new` command from the src/day<n>.rs files, do not edit by hand.
+// Included by lib.rs, so that the day modules are declared at the root of the crate.
+
+"
+    .to_owned();
+
+    for day in days {
+        let _ = writeln!(registry, "pub mod day{day};");
+    }
+
+    registry.push_str(
+        "\n/// Solvers of every implemented day, in day order.\npub const SOLVERS: &[Solver] = &[\n",
+    );
+
+    for day in days {
+        let _ = writeln!(registry, "    Solver::of::<day{day}::Day{day}>(),");
+    }
+
+    registry.push_str("];\n");
+    registry
+}
+
+/// Days that have a `day<n>.rs` solution file in `src_dir`.
+fn registered_days(src_dir: &Path) -> Result<Vec<usize>, ScaffoldError> {
+    let entries =
+        fs::read_dir(src_dir).map_err(|err| ScaffoldError::IoError(src_dir.to_owned(), err))?;
+    let mut days = vec![];
+
+    for entry in entries {
+        let entry = entry.map_err(|err| ScaffoldError::IoError(src_dir.to_owned(), err))?;
+        let filename = entry.file_name();
+
+        let day = filename
+            .to_str()
+            .and_then(|filename| filename.strip_prefix("day"))
+            .and_then(|filename| filename.strip_suffix(".rs"))
+            .and_then(|day| day.parse::<usize>().ok());
+
+        if let Some(day) = day {
+            days.push(day);
+        }
+    }
+
+    days.sort_unstable();
+
+    Ok(days)
+}
+
+fn create_new(path: &Path, content: &str) -> Result<(), ScaffoldError> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(|err| ScaffoldError::IoError(path.to_owned(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{new_day, render_registry, render_template, ScaffoldError};
+    use crate::testing::TempDir;
+
+    /// A crate layout with day 1 and a template, removed when dropped.
+    fn temp_crate(name: &str) -> TempDir {
+        let temp_crate = TempDir::new(&format!("scaffold-{name}"));
+        let root = temp_crate.path();
+
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("inputs/day2")).unwrap();
+        fs::write(root.join("src/day1.rs"), "").unwrap();
+        fs::write(root.join("inputs/day2/input.txt"), "kept").unwrap();
+        fs::write(root.join("day.template"), "pub struct DayN;\n").unwrap();
+
+        temp_crate
+    }
+
+    #[test]
+    fn template_is_named_after_the_day() {
+        assert_eq!(
+            render_template("impl Solution for DayN {}\ntest_solvers!(DayN);", 14),
+            "impl Solution for Day14 {}\ntest_solvers!(Day14);"
+        );
+    }
+
+    #[test]
+    fn registry_lists_days_in_order() {
+        let registry = render_registry(&[1, 2, 10]);
+
+        assert!(registry.contains("pub mod day1;\npub mod day2;\npub mod day10;\n"));
+        assert!(registry.ends_with(
+            "    Solver::of::<day1::Day1>(),\n    \
+             Solver::of::<day2::Day2>(),\n    \
+             Solver::of::<day10::Day10>(),\n];\n"
+        ));
+    }
+
+    #[test]
+    fn new_day_creates_files_without_overwriting() {
+        let temp_crate = temp_crate("create");
+        let root = temp_crate.path();
+
+        let created = new_day(root, 2).unwrap();
+
+        assert_eq!(
+            created,
+            [root.join("src/day2.rs"), root.join("inputs/day2/test.txt")]
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("src/day2.rs")).unwrap(),
+            "pub struct Day2;\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("inputs/day2/input.txt")).unwrap(),
+            "kept"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("src/registry.rs")).unwrap(),
+            render_registry(&[1, 2])
+        );
+
+        assert!(matches!(
+            new_day(root, 2),
+            Err(ScaffoldError::DayExistsError(path)) if path == root.join("src/day2.rs")
+        ));
+    }
+
+    #[test]
+    fn new_day_needs_the_previous_day() {
+        let temp_crate = temp_crate("gap");
+
+        assert!(matches!(
+            new_day(temp_crate.path(), 4),
+            Err(ScaffoldError::MissingPreviousDayError(3))
+        ));
+        assert!(!temp_crate.path().join("src/day4.rs").exists());
+    }
+}
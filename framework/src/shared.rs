@@ -0,0 +1,509 @@
+use std::{
+    any::Any,
+    error::Error,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    num::ParseIntError,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::generate::Rng;
+
+/// Answer of a part. Numbers compare by value whatever their variant, so an answer computed as a
+/// `u64` equals the same answer computed as an `i64`.
+#[derive(Debug, Clone)]
+pub enum PuzzleResult {
+    Text(String),
+    Num(i64),
+    Unsigned(u64),
+    Num128(i128),
+    Unsigned128(u128),
+    /// A picture drawn by the solver, such as the letters of a CRT screen.
+    Grid(Grid),
+}
+
+impl PuzzleResult {
+    /// Sign and magnitude of a numeric answer, which orders every integer variant the same way.
+    fn numeric(&self) -> Option<(bool, u128)> {
+        match *self {
+            Self::Num(value) => Some((value < 0, value.unsigned_abs().into())),
+            Self::Unsigned(value) => Some((false, value.into())),
+            Self::Num128(value) => Some((value < 0, value.unsigned_abs())),
+            Self::Unsigned128(value) => Some((false, value)),
+            Self::Text(_) | Self::Grid(_) => None,
+        }
+    }
+}
+
+impl PartialEq for PuzzleResult {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Text(value), Self::Text(other_value)) => value == other_value,
+            (Self::Grid(grid), Self::Grid(other_grid)) => grid == other_grid,
+            _ => self.numeric().is_some() && self.numeric() == other.numeric(),
+        }
+    }
+}
+
+impl Eq for PuzzleResult {}
+
+macro_rules! impl_from_integer {
+    ($variant:ident, $target:ty: $($integer:ty),+) => {
+        $(
+            impl From<$integer> for PuzzleResult {
+                fn from(value: $integer) -> Self {
+                    Self::$variant(<$target>::from(value))
+                }
+            }
+        )+
+    };
+}
+
+impl_from_integer!(Num, i64: i8, i16, i32, i64, u8, u16, u32);
+impl_from_integer!(Unsigned, u64: u64);
+impl_from_integer!(Num128, i128: i128);
+impl_from_integer!(Unsigned128, u128: u128);
+
+impl From<isize> for PuzzleResult {
+    fn from(value: isize) -> Self {
+        Self::Num(value as i64)
+    }
+}
+
+impl From<usize> for PuzzleResult {
+    fn from(value: usize) -> Self {
+        Self::Unsigned(value as u64)
+    }
+}
+
+impl From<String> for PuzzleResult {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for PuzzleResult {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_owned())
+    }
+}
+
+impl From<Grid> for PuzzleResult {
+    fn from(value: Grid) -> Self {
+        Self::Grid(value)
+    }
+}
+
+impl Display for PuzzleResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PuzzleResult::Text(value) => value.fmt(f),
+            PuzzleResult::Num(value) => value.fmt(f),
+            PuzzleResult::Unsigned(value) => value.fmt(f),
+            PuzzleResult::Num128(value) => value.fmt(f),
+            PuzzleResult::Unsigned128(value) => value.fmt(f),
+            PuzzleResult::Grid(grid) => grid.fmt(f),
+        }
+    }
+}
+
+/// A picture of lit and dark cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    width: usize,
+    cells: Vec<bool>,
+}
+
+impl Grid {
+    /// Creates a grid with every cell dark.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            cells: vec![false; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len().checked_div(self.width).unwrap_or(0)
+    }
+
+    /// Whether the cell is lit. Cells outside of the grid are dark.
+    pub fn get(&self, column: usize, row: usize) -> bool {
+        column < self.width && self.cells.get(row * self.width + column) == Some(&true)
+    }
+
+    /// Lights up or darkens a cell.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is outside of the grid.
+    pub fn set(&mut self, column: usize, row: usize, lit: bool) {
+        assert!(
+            column < self.width && row < self.height(),
+            "cell ({column}, {row}) should be within the {}x{} grid",
+            self.width,
+            self.height()
+        );
+
+        self.cells[row * self.width + column] = lit;
+    }
+}
+
+impl Display for Grid {
+    /// Draws lit cells as `#` and dark ones as `.`. The picture starts on a new line, so that it
+    /// stays aligned when printed after a label.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f)?;
+
+        for row in self.cells.chunks(self.width.max(1)) {
+            for &lit in row {
+                write!(f, "{}", if lit { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// A character other than `#` and `.`, with its 1-based line.
+    InvalidCellError(char, usize),
+    /// A line whose width differs from the first line's, with its 1-based line.
+    RaggedLineError(usize),
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidCellError(cell, line) => {
+                write!(f, "cell should be '#' or '.', got '{cell}' on line {line}")
+            }
+            Self::RaggedLineError(line) => {
+                write!(f, "line {line} should be as wide as the first one")
+            }
+        }
+    }
+}
+
+impl Error for ParseGridError {}
+
+impl FromStr for Grid {
+    type Err = ParseGridError;
+
+    /// Reads a drawing as produced by `Display`, ignoring the line breaks around it.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = s.trim_matches('\n').lines().collect::<Vec<_>>();
+        let width = lines.first().map_or(0, |line| line.chars().count());
+        let mut grid = Self::new(width, lines.len());
+
+        for (row, line) in lines.iter().enumerate() {
+            if line.chars().count() != width {
+                return Err(ParseGridError::RaggedLineError(row + 1));
+            }
+
+            for (column, cell) in line.chars().enumerate() {
+                match cell {
+                    '#' => grid.set(column, row, true),
+                    '.' => {}
+                    _ => return Err(ParseGridError::InvalidCellError(cell, row + 1)),
+                }
+            }
+        }
+
+        Ok(grid)
+    }
+}
+
+#[derive(Debug)]
+pub enum SolveError {
+    /// The input could not be parsed, wrapping the day's parse error.
+    ParseError(Box<dyn Error + Send + Sync>),
+    /// The input was parsed but does not describe a puzzle that can be solved.
+    InvalidInputError(String),
+}
+
+impl SolveError {
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::InvalidInputError(message.into())
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ParseError(err) => write!(f, "invalid input: {err}"),
+            Self::InvalidInputError(message) => write!(f, "invalid input: {message}"),
+        }
+    }
+}
+
+impl Error for SolveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::ParseError(err) => Some(err.as_ref()),
+            Self::InvalidInputError(_) => None,
+        }
+    }
+}
+
+impl From<ParseIntError> for SolveError {
+    fn from(err: ParseIntError) -> Self {
+        Self::ParseError(Box::new(err))
+    }
+}
+
+/// A day's puzzle solution. The input is parsed once and the parsed value is shared by both parts.
+pub trait Solution {
+    type Parsed: Send + Sync + 'static;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError>;
+
+    fn part1(parsed: &Self::Parsed) -> Result<PuzzleResult, SolveError>;
+
+    fn part2(parsed: &Self::Parsed) -> Result<PuzzleResult, SolveError>;
+
+    /// Naive solutions of the parts, slow but obviously right, that the `diff` command checks the
+    /// solution against on generated inputs. They parse the input on their own.
+    const REFERENCES: [Option<ReferenceSolver>; 2] = [None, None];
+
+    /// Writes a random valid input, the same for the same state of `rng`. `size` scales the input
+    /// as suits the day, e.g. its number of lines. Days without a generator return `None`.
+    fn generate(_rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+}
+
+/// Parsed input of a [`Solver`], holding the `Parsed` value of its solution.
+pub type ParsedInput = Box<dyn Any + Send + Sync>;
+
+/// Solves one part of a [`ParsedInput`].
+pub type PartSolver = fn(&ParsedInput) -> Result<PuzzleResult, SolveError>;
+
+/// Solves one part of a raw input, see [`Solution::REFERENCES`].
+pub type ReferenceSolver = fn(&str) -> Result<PuzzleResult, SolveError>;
+
+/// A [`Solution`] with its parsed input type erased, so that every day fits in the registry.
+#[derive(Clone, Copy)]
+pub struct Solver {
+    pub parse: fn(&str) -> Result<ParsedInput, SolveError>,
+    pub parts: [PartSolver; 2],
+    pub generate: fn(&mut Rng, usize) -> Option<String>,
+    pub references: [Option<ReferenceSolver>; 2],
+}
+
+impl Solver {
+    pub const fn of<S: Solution>() -> Self {
+        Self {
+            parse: parse_erased::<S>,
+            parts: [part1_erased::<S>, part2_erased::<S>],
+            generate: S::generate,
+            references: S::REFERENCES,
+        }
+    }
+}
+
+fn parse_erased<S: Solution>(input: &str) -> Result<ParsedInput, SolveError> {
+    Ok(Box::new(S::parse(input)?))
+}
+
+fn part1_erased<S: Solution>(parsed: &ParsedInput) -> Result<PuzzleResult, SolveError> {
+    S::part1(downcast_parsed::<S>(parsed))
+}
+
+fn part2_erased<S: Solution>(parsed: &ParsedInput) -> Result<PuzzleResult, SolveError> {
+    S::part2(downcast_parsed::<S>(parsed))
+}
+
+fn downcast_parsed<S: Solution>(parsed: &ParsedInput) -> &S::Parsed {
+    parsed
+        .downcast_ref()
+        .expect("parsed input should come from the parse function of the same solution")
+}
+
+/// An Advent of Code edition: the year and the solvers of its days, in day order.
+///
+/// Every year crate exports one, so that the binary can pick it with `--year`.
+#[derive(Clone, Copy)]
+pub struct Edition {
+    pub year: u16,
+    /// Directory of the year's crate, holding its `src/` and `inputs/` directories. Year crates set
+    /// it to their `CARGO_MANIFEST_DIR` so that the binary can be run from any working directory.
+    pub root: &'static str,
+    pub solvers: &'static [Solver],
+}
+
+impl Edition {
+    /// Directory holding the inputs of the day, `inputs/day<n>` in the year's crate.
+    pub fn day_dir(&self, day: usize) -> PathBuf {
+        Path::new(self.root)
+            .join("inputs")
+            .join(format!("day{day}"))
+    }
+}
+
+/// Which of a day's inputs to run on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataSet {
+    Test,
+    Real,
+}
+
+impl DataSet {
+    pub fn filename(&self) -> &'static str {
+        match self {
+            Self::Test => "test",
+            Self::Real => "input",
+        }
+    }
+}
+
+impl Display for DataSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Test => write!(f, "test"),
+            Self::Real => write!(f, "real"),
+        }
+    }
+}
+
+pub fn input_path(day_dir: &Path, filename: &str) -> PathBuf {
+    day_dir.join(format!("{filename}.txt"))
+}
+
+pub fn read_input(day_dir: &Path, filename: &str) -> io::Result<String> {
+    fs::read_to_string(input_path(day_dir, filename))
+}
+
+/// Names of the day's example inputs: every `test*.txt` file in its inputs folder, excluding
+/// `*.answers.txt` sidecars, sorted by name.
+pub fn example_names(day_dir: &Path) -> io::Result<Vec<String>> {
+    let mut names = vec![];
+
+    for entry in fs::read_dir(day_dir)? {
+        let filename = entry?.file_name();
+        let Some(filename) = filename.to_str() else {
+            continue;
+        };
+
+        if let Some(name) = filename.strip_suffix(".txt") {
+            if name.starts_with("test") && !name.ends_with(".answers") {
+                names.push(name.to_owned());
+            }
+        }
+    }
+
+    names.sort();
+
+    Ok(names)
+}
+
+pub fn read_stdin() -> io::Result<String> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+
+    Ok(input)
+}
+
+/// Generates the tests of a day's solution, checking it against the examples with expected
+/// answers and the recorded answers for the real input in the `inputs/` directory of the crate
+/// calling it.
+#[macro_export]
+macro_rules! test_solvers {
+    ($solution:ident) => {
+        #[cfg(test)]
+        mod tests {
+            use std::path::{Path, PathBuf};
+
+            use $crate::shared::Solver;
+
+            const SOLVER: Solver = Solver::of::<super::$solution>();
+
+            fn get_day_dir() -> PathBuf {
+                // the module path is `<crate>::day<n>::tests`, whatever the crate is named
+                let day_folder = module_path!()
+                    .split("::")
+                    .nth(1)
+                    .expect("module path should equal to '<crate>::day<n>::tests'");
+
+                Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join("inputs")
+                    .join(day_folder)
+            }
+
+            #[test]
+            fn part1_returns_expected_results_for_examples() {
+                $crate::answers::assert_example_answers(&get_day_dir(), 1, &SOLVER);
+            }
+
+            #[test]
+            fn part2_returns_expected_results_for_examples() {
+                $crate::answers::assert_example_answers(&get_day_dir(), 2, &SOLVER);
+            }
+
+            #[test]
+            fn part1_returns_recorded_answer_for_real_input() {
+                $crate::answers::assert_recorded_answer(&get_day_dir(), 1, &SOLVER);
+            }
+
+            #[test]
+            fn part2_returns_recorded_answer_for_real_input() {
+                $crate::answers::assert_recorded_answer(&get_day_dir(), 2, &SOLVER);
+            }
+
+            #[test]
+            fn generated_inputs_are_solved() {
+                $crate::generate::assert_generated_inputs_are_solved(&SOLVER);
+            }
+
+            #[test]
+            fn reference_solutions_agree_on_generated_inputs() {
+                $crate::differential::assert_references_agree(&SOLVER);
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{Grid, ParseGridError, PuzzleResult};
+
+    #[test]
+    fn numbers_are_equal_whatever_their_type() {
+        assert_eq!(PuzzleResult::from(42_u64), PuzzleResult::from(42_i32));
+        assert_eq!(PuzzleResult::from(42_usize), PuzzleResult::from(42_u128));
+        assert_eq!(PuzzleResult::from(-7_i128), PuzzleResult::from(-7_i8));
+        assert_ne!(PuzzleResult::from(-7_i64), PuzzleResult::from(7_u64));
+        assert_ne!(PuzzleResult::from(42), PuzzleResult::from("42"));
+        assert_eq!(
+            PuzzleResult::from(u64::MAX as u128 * 3).to_string(),
+            "55340232221128654845"
+        );
+    }
+
+    #[test]
+    fn grid_is_drawn_below_its_label() {
+        let mut grid = Grid::new(3, 2);
+        grid.set(0, 0, true);
+        grid.set(2, 1, true);
+
+        assert!(grid.get(2, 1));
+        assert!(!grid.get(3, 0));
+        assert_eq!(grid.height(), 2);
+        assert_eq!(Grid::from_str(&grid.to_string()), Ok(grid.clone()));
+        assert_eq!(PuzzleResult::from(grid).to_string(), "\n#..\n..#\n");
+        assert_eq!(
+            Grid::from_str("#..\n.#"),
+            Err(ParseGridError::RaggedLineError(2))
+        );
+    }
+}
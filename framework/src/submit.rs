@@ -0,0 +1,517 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::{
+    answers,
+    client::{Client, ClientError},
+};
+
+/// What the server made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without telling whether the answer is too high or too low.
+    Wrong,
+    /// The answer was not checked as the previous one was too recent, with the time left to wait.
+    RateLimited(Option<Duration>),
+    /// The part was already solved, or is not unlocked yet.
+    WrongLevel,
+}
+
+impl SubmitOutcome {
+    /// Parses the page the server answers a submission with, or returns `None` when it has none of
+    /// the known messages.
+    pub fn from_response(page: &str) -> Option<Self> {
+        let outcome = if page.contains("That's the right answer") {
+            Self::Correct
+        } else if page.contains("your answer is too high") {
+            Self::TooHigh
+        } else if page.contains("your answer is too low") {
+            Self::TooLow
+        } else if page.contains("That's not the right answer") {
+            Self::Wrong
+        } else if page.contains("You gave an answer too recently") {
+            Self::RateLimited(wait_time(page))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Self::WrongLevel
+        } else {
+            return None;
+        };
+
+        Some(outcome)
+    }
+}
+
+impl Display for SubmitOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh => write!(f, "wrong, too high"),
+            Self::TooLow => write!(f, "wrong, too low"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::RateLimited(Some(wait)) => {
+                write!(f, "rate-limited, wait {}s", wait.as_secs())
+            }
+            Self::RateLimited(None) => write!(f, "rate-limited"),
+            Self::WrongLevel => write!(f, "already solved or not unlocked yet"),
+        }
+    }
+}
+
+/// Parses the time left to wait from a "You have 1m 5s left to wait." message.
+fn wait_time(page: &str) -> Option<Duration> {
+    let (_, rest) = page.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+
+    wait.split_whitespace()
+        .try_fold(Duration::ZERO, |total, amount| {
+            let unit_index = amount.len().checked_sub(1)?;
+            let (value, unit) = amount.split_at(unit_index);
+            let value = value.parse::<u64>().ok()?;
+
+            let seconds = match unit {
+                "h" => value * 3600,
+                "m" => value * 60,
+                "s" => value,
+                _ => return None,
+            };
+
+            Some(total + Duration::from_secs(seconds))
+        })
+}
+
+/// A submitted answer, with the Unix time in seconds it was submitted at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub part: usize,
+    pub answer: String,
+    pub submitted_at: u64,
+    pub outcome: SubmitOutcome,
+}
+
+/// Why an answer is not submitted, as the history tells what the server would answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    KnownWrong,
+    /// The answer is at least an answer known to be too high.
+    AboveBound(String),
+    /// The answer is at most an answer known to be too low.
+    BelowBound(String),
+    /// The server asked to wait before submitting again, with the time left.
+    Throttled(Duration),
+    /// Multi-line answers (e.g. CRT screens) have to be read and submitted by hand.
+    MultiLine,
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AlreadySolved(answer) => write!(f, "the part was already solved with {answer}"),
+            Self::KnownWrong => write!(f, "the answer was already submitted and is wrong"),
+            Self::AboveBound(bound) => {
+                write!(
+                    f,
+                    "the answer should be lower than {bound}, which is too high"
+                )
+            }
+            Self::BelowBound(bound) => {
+                write!(
+                    f,
+                    "the answer should be higher than {bound}, which is too low"
+                )
+            }
+            Self::Throttled(wait) => {
+                write!(f, "the server asked to wait {}s more", wait.as_secs())
+            }
+            Self::MultiLine => write!(f, "multi-line answers should be submitted by hand"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    RefusedError(Refusal),
+    ClientError(ClientError),
+    /// The server answered with a page without any of the known messages.
+    UnknownResponseError,
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RefusedError(refusal) => write!(f, "not submitting: {refusal}"),
+            Self::ClientError(err) => err.fmt(f),
+            Self::UnknownResponseError => write!(f, "could not make sense of the response page"),
+        }
+    }
+}
+
+impl From<ClientError> for SubmitError {
+    fn from(err: ClientError) -> Self {
+        Self::ClientError(err)
+    }
+}
+
+/// Every answer submitted for one of a day's parts, stored in `inputs/day<n>/submissions.txt` as
+/// `part<n>\t<unix time>\t<outcome>\t<answer>` lines, oldest first.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct History {
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    pub fn path(day_dir: &Path) -> PathBuf {
+        day_dir.join("submissions.txt")
+    }
+
+    /// Loads the history of the day, treating a missing file as no submission yet.
+    pub fn load(day_dir: &Path) -> io::Result<Self> {
+        match fs::read_to_string(Self::path(day_dir)) {
+            Ok(content) => Self::from_str(&content)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, day_dir: &Path) -> io::Result<()> {
+        fs::write(Self::path(day_dir), self.to_string())
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt);
+    }
+
+    /// Checks the answer against the previous attempts of the day, refusing it when the server
+    /// would not accept it or is known to reject it.
+    pub fn check(&self, part: usize, answer: &str, now: u64) -> Result<(), Refusal> {
+        if answer.trim().contains('\n') {
+            return Err(Refusal::MultiLine);
+        }
+
+        let part_attempts = self.attempts.iter().filter(|attempt| attempt.part == part);
+        let numeric_answer = answer.parse::<i128>().ok();
+
+        for attempt in part_attempts {
+            let bound = attempt.answer.parse::<i128>().ok();
+
+            match (attempt.outcome, numeric_answer, bound) {
+                (SubmitOutcome::Correct, _, _) => {
+                    return Err(Refusal::AlreadySolved(attempt.answer.clone()))
+                }
+                (SubmitOutcome::TooHigh | SubmitOutcome::TooLow | SubmitOutcome::Wrong, _, _)
+                    if attempt.answer == answer =>
+                {
+                    return Err(Refusal::KnownWrong)
+                }
+                (SubmitOutcome::TooHigh, Some(value), Some(bound)) if value >= bound => {
+                    return Err(Refusal::AboveBound(attempt.answer.clone()))
+                }
+                (SubmitOutcome::TooLow, Some(value), Some(bound)) if value <= bound => {
+                    return Err(Refusal::BelowBound(attempt.answer.clone()))
+                }
+                _ => {}
+            }
+        }
+
+        // the server throttles submissions whatever the day and part, so a wait asked for either
+        // part counts, but the history is the day's: a wait asked on another day is not seen here
+        // and the server's answer to the submission is recorded instead
+        let throttled_until = self
+            .attempts
+            .iter()
+            .filter_map(|attempt| match attempt.outcome {
+                SubmitOutcome::RateLimited(Some(wait)) => {
+                    Some(attempt.submitted_at + wait.as_secs())
+                }
+                _ => None,
+            })
+            .max();
+
+        match throttled_until {
+            Some(until) if until > now => Err(Refusal::Throttled(Duration::from_secs(until - now))),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseHistoryError(String);
+
+impl Display for ParseHistoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expected a 'part<n>\\t<time>\\t<outcome>\\t<answer>' line, got '{}'",
+            self.0
+        )
+    }
+}
+
+impl FromStr for History {
+    type Err = ParseHistoryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let attempts = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| parse_attempt(line).ok_or_else(|| ParseHistoryError(line.to_owned())))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { attempts })
+    }
+}
+
+fn parse_attempt(line: &str) -> Option<Attempt> {
+    let mut fields = line.splitn(4, '\t');
+
+    let part = match fields.next()? {
+        "part1" => 1,
+        "part2" => 2,
+        _ => return None,
+    };
+    let submitted_at = fields.next()?.parse().ok()?;
+    let outcome = match fields.next()? {
+        "correct" => SubmitOutcome::Correct,
+        "too-high" => SubmitOutcome::TooHigh,
+        "too-low" => SubmitOutcome::TooLow,
+        "wrong" => SubmitOutcome::Wrong,
+        "rate-limited" => SubmitOutcome::RateLimited(None),
+        "wrong-level" => SubmitOutcome::WrongLevel,
+        outcome => {
+            let wait = outcome.strip_prefix("rate-limited:")?.parse().ok()?;
+            SubmitOutcome::RateLimited(Some(Duration::from_secs(wait)))
+        }
+    };
+    let answer = answers::unescape(fields.next()?);
+
+    Some(Attempt {
+        part,
+        answer,
+        submitted_at,
+        outcome,
+    })
+}
+
+impl Display for History {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for attempt in &self.attempts {
+            let outcome = match attempt.outcome {
+                SubmitOutcome::Correct => "correct".to_owned(),
+                SubmitOutcome::TooHigh => "too-high".to_owned(),
+                SubmitOutcome::TooLow => "too-low".to_owned(),
+                SubmitOutcome::Wrong => "wrong".to_owned(),
+                SubmitOutcome::RateLimited(None) => "rate-limited".to_owned(),
+                SubmitOutcome::RateLimited(Some(wait)) => {
+                    format!("rate-limited:{}", wait.as_secs())
+                }
+                SubmitOutcome::WrongLevel => "wrong-level".to_owned(),
+            };
+
+            writeln!(
+                f,
+                "part{}\t{}\t{outcome}\t{}",
+                attempt.part,
+                attempt.submitted_at,
+                answers::escape(&attempt.answer)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Submits the answer to one part of the day unless the history refuses it, and records the
+/// attempt into the history. The caller saves the history.
+pub fn submit_answer(
+    client: &Client,
+    year: u16,
+    day: usize,
+    part: usize,
+    answer: &str,
+    history: &mut History,
+    now: u64,
+) -> Result<SubmitOutcome, SubmitError> {
+    history
+        .check(part, answer, now)
+        .map_err(SubmitError::RefusedError)?;
+
+    let level = part.to_string();
+    let page = client.post_form(
+        &format!("/{year}/day/{day}/answer"),
+        &[("level", &level), ("answer", answer)],
+    )?;
+    let outcome = SubmitOutcome::from_response(&page).ok_or(SubmitError::UnknownResponseError)?;
+
+    history.record(Attempt {
+        part,
+        answer: answer.to_owned(),
+        submitted_at: now,
+        outcome,
+    });
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, time::Duration};
+
+    use super::{submit_answer, Attempt, History, Refusal, SubmitError, SubmitOutcome};
+    use crate::client::{test_server, Client};
+
+    fn attempt(part: usize, answer: &str, submitted_at: u64, outcome: SubmitOutcome) -> Attempt {
+        Attempt {
+            part,
+            answer: answer.to_owned(),
+            submitted_at,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn response_pages_are_recognized() {
+        let page = |message: &str| format!("<main>\n<article><p>{message}</p></article>\n</main>");
+
+        assert_eq!(
+            SubmitOutcome::from_response(&page(
+                "That's the right answer!  You are one gold star closer."
+            )),
+            Some(SubmitOutcome::Correct)
+        );
+        assert_eq!(
+            SubmitOutcome::from_response(&page(
+                "That's not the right answer; your answer is too high."
+            )),
+            Some(SubmitOutcome::TooHigh)
+        );
+        assert_eq!(
+            SubmitOutcome::from_response(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Some(SubmitOutcome::TooLow)
+        );
+        assert_eq!(
+            SubmitOutcome::from_response(&page(
+                "That's not the right answer.  If you're stuck, ..."
+            )),
+            Some(SubmitOutcome::Wrong)
+        );
+        assert_eq!(
+            SubmitOutcome::from_response(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 1m 5s left to wait."
+            )),
+            Some(SubmitOutcome::RateLimited(Some(Duration::from_secs(65))))
+        );
+        assert_eq!(
+            SubmitOutcome::from_response(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Some(SubmitOutcome::WrongLevel)
+        );
+        assert_eq!(SubmitOutcome::from_response(&page("Hello")), None);
+    }
+
+    #[test]
+    fn history_round_trips_through_the_file_format() {
+        let mut history = History::default();
+        history.record(attempt(1, "100", 1000, SubmitOutcome::TooHigh));
+        history.record(attempt(
+            1,
+            "50",
+            1010,
+            SubmitOutcome::RateLimited(Some(Duration::from_secs(40))),
+        ));
+        history.record(attempt(2, "ABC", 1100, SubmitOutcome::Correct));
+
+        let content = history.to_string();
+
+        assert_eq!(
+            content,
+            "part1\t1000\ttoo-high\t100\npart1\t1010\trate-limited:40\t50\npart2\t1100\tcorrect\tABC\n"
+        );
+        assert_eq!(History::from_str(&content), Ok(history));
+    }
+
+    #[test]
+    fn check_refuses_answers_the_history_rules_out() {
+        let history = History::from_str(
+            "part1\t1000\ttoo-high\t100\n\
+             part1\t1100\ttoo-low\t20\n\
+             part1\t1200\twrong\t42\n\
+             part2\t1300\tcorrect\t7\n",
+        )
+        .unwrap();
+
+        assert_eq!(history.check(1, "42", 2000), Err(Refusal::KnownWrong));
+        assert_eq!(history.check(1, "100", 2000), Err(Refusal::KnownWrong));
+        assert_eq!(
+            history.check(1, "150", 2000),
+            Err(Refusal::AboveBound("100".to_owned()))
+        );
+        assert_eq!(
+            history.check(1, "3", 2000),
+            Err(Refusal::BelowBound("20".to_owned()))
+        );
+        assert_eq!(
+            history.check(2, "8", 2000),
+            Err(Refusal::AlreadySolved("7".to_owned()))
+        );
+        assert_eq!(history.check(1, "#..\n.#.", 2000), Err(Refusal::MultiLine));
+        assert_eq!(history.check(1, "50", 2000), Ok(()));
+    }
+
+    #[test]
+    fn check_waits_for_the_rate_limit() {
+        let history = History::from_str("part1\t1000\trate-limited:60\t50\n").unwrap();
+
+        assert_eq!(
+            history.check(1, "51", 1045),
+            Err(Refusal::Throttled(Duration::from_secs(15)))
+        );
+        assert_eq!(history.check(1, "51", 1060), Ok(()));
+    }
+
+    #[test]
+    fn submission_is_posted_and_recorded() {
+        let (base_url, requests) = test_server::serve(vec![(
+            200,
+            "<article><p>That's not the right answer; your answer is too low.</p></article>",
+        )]);
+        let client = Client::new(&base_url, Some("secret".to_owned()));
+        let mut history = History::default();
+
+        let outcome = submit_answer(&client, 2022, 3, 2, "1234", &mut history, 1000).unwrap();
+
+        assert_eq!(outcome, SubmitOutcome::TooLow);
+        assert_eq!(
+            history.attempts(),
+            [attempt(2, "1234", 1000, SubmitOutcome::TooLow)]
+        );
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2022/day/3/answer HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=secret\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=1234"));
+
+        // the server is not asked again about an answer the history rules out
+        assert!(matches!(
+            submit_answer(&client, 2022, 3, 2, "1000", &mut history, 2000),
+            Err(SubmitError::RefusedError(Refusal::BelowBound(_)))
+        ));
+        assert!(requests.try_recv().is_err());
+    }
+}
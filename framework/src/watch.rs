@@ -0,0 +1,146 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::answers::{self, Answers, Verdict};
+
+/// Modification times of the files a day depends on: `src/day<n>.rs`, the files under `src/day<n>/`
+/// and those under `inputs/day<n>/`, in the year's crate. Comparing two snapshots tells whether the
+/// day should be built and run again, without relying on a file-watching service.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    modified: BTreeMap<PathBuf, SystemTime>,
+}
+
+impl Snapshot {
+    pub fn take(root: &Path, day: usize) -> io::Result<Self> {
+        let mut snapshot = Self::default();
+        let src = root.join("src");
+
+        snapshot.add(&src.join(format!("day{day}.rs")))?;
+        snapshot.add_dir(&src.join(format!("day{day}")))?;
+        snapshot.add_dir(&root.join("inputs").join(format!("day{day}")))?;
+
+        Ok(snapshot)
+    }
+
+    fn add(&mut self, path: &Path) -> io::Result<()> {
+        match fs::metadata(path) {
+            Ok(metadata) => {
+                self.modified.insert(path.to_owned(), metadata.modified()?);
+                Ok(())
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn add_dir(&mut self, dir: &Path) -> io::Result<()> {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err),
+        };
+
+        for entry in entries {
+            let path = entry?.path();
+
+            if path.is_dir() {
+                self.add_dir(&path)?;
+            } else {
+                self.add(&path)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// One line per part comparing the answers of a run with those of the previous run, if any, and
+/// with the expected answers. Answers are shown escaped, so each part stays on a single line:
+///
+/// ```text
+/// part 1: 95437 ✓
+/// part 2: 24933641 (was 24933640) ✗ expected 24933642
+/// ```
+pub fn diff(previous: Option<&Answers>, current: &Answers, expected: &Answers) -> Vec<String> {
+    let show = |answer: Option<&str>| answer.map_or_else(|| "failed".to_owned(), answers::escape);
+
+    (1..=2)
+        .map(|part| {
+            let answer = current.get(part);
+            let mut line = format!("part {part}: {}", show(answer));
+
+            if let Some(previous_answer) = previous
+                .map(|previous| previous.get(part))
+                .filter(|&previous_answer| previous_answer != answer)
+            {
+                line.push_str(&format!(" (was {})", show(previous_answer)));
+            }
+
+            let verdict = match (expected.get(part), answer) {
+                (None, _) => Verdict::Unknown.to_string(),
+                (Some(expected), Some(answer)) if expected == answer => {
+                    Verdict::Correct.to_string()
+                }
+                (Some(expected), _) => format!(
+                    "{} expected {}",
+                    Verdict::Wrong(expected.to_owned()),
+                    answers::escape(expected)
+                ),
+            };
+            line.push_str(&format!(" {verdict}"));
+
+            line
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, str::FromStr, thread, time::Duration};
+
+    use super::{diff, Snapshot};
+    use crate::{answers::Answers, testing::TempDir};
+
+    #[test]
+    fn snapshot_changes_with_the_day_files_only() {
+        let temp_dir = TempDir::new("watch");
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src/day3")).unwrap();
+        fs::create_dir_all(root.join("inputs/day3")).unwrap();
+        fs::write(root.join("src/day3.rs"), "").unwrap();
+        fs::write(root.join("src/day3/rucksack.rs"), "").unwrap();
+        fs::write(root.join("src/day30.rs"), "").unwrap();
+
+        let snapshot = Snapshot::take(root, 3).unwrap();
+        assert_eq!(snapshot.modified.len(), 2);
+
+        fs::write(root.join("src/day30.rs"), "changed").unwrap();
+        assert_eq!(Snapshot::take(root, 3).unwrap(), snapshot);
+
+        // leaves time for a coarse file system clock to tick
+        thread::sleep(Duration::from_millis(20));
+        fs::write(root.join("inputs/day3/test.txt"), "vJrwpWtwJgWr").unwrap();
+        assert_ne!(Snapshot::take(root, 3).unwrap(), snapshot);
+    }
+
+    #[test]
+    fn diff_shows_changes_and_verdicts() {
+        let previous = Answers::from_str("part1: 95437\npart2: 1\n").unwrap();
+        let current = Answers::from_str("part1: 95437\n").unwrap();
+        let expected = Answers::from_str("part1: 95437\npart2: a\\nb\n").unwrap();
+
+        assert_eq!(
+            diff(Some(&previous), &current, &expected),
+            ["part 1: 95437 ✓", "part 2: failed (was 1) ✗ expected a\\nb"]
+        );
+        assert_eq!(
+            diff(None, &previous, &Answers::default()),
+            ["part 1: 95437 ?", "part 2: 1 ?"]
+        );
+    }
+}
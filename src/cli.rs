@@ -0,0 +1,982 @@
+use std::{fmt::Display, ops::Range, path::PathBuf};
+
+use aoc_framework::{bench::BenchOptions, scaffold, shared::DataSet};
+
+pub const USAGE: &str = "\
+Usage: advent-of-code [--year <year>] <command> [arguments]
+
+Commands:
+  run <day> [part] [--test | --input <path>] [--record] [--format <format>]
+                             Run the solver for a day (both parts when part is omitted)
+  watch <day>                Rebuild and run a day on its test and real inputs whenever its sources
+                             or inputs change, showing how the answers changed
+  test <day>                 Check both parts of a day against every test*.txt example
+  all [--test] [--record] [--format <format>] [--jobs <n>]
+                             Run both parts of every day and print a summary table
+  bench [day] [part] [--input <path>] [--compare [--threshold <percent>]]
+                             Measure solver timings (every day when day is omitted). Medians for
+                             the real inputs are appended to bench_history.txt, or with --compare
+                             checked against the last ones recorded instead
+  gen <day> [--seed <n>] [--size <n>]
+                             Print a random input for a day, the same for the same seed and size,
+                             e.g. to pipe into 'bench <day> --input -'
+  diff <day> [--seed <n>] [--seeds <n>] [--size <n>]
+                             Check the parts of a day having a naive reference solution against it
+                             on the inputs generated for the seeds from --seed on, printing the
+                             first mismatching input shrunk
+  report [--timings]         Run every day on its real input and rewrite the table of puzzles, stars
+                             and verified answers in the year's README.md
+  fetch <day>                Download the real input of a day into inputs/day<n>/input.txt, unless
+                             already there. The session token is read from $AOC_SESSION or
+                             ~/.config/aoc/session, and $AOC_BASE_URL overrides the server
+  submit <day> <part>        Solve a part on the real input and submit the answer, unless the
+                             history in inputs/day<n>/submissions.txt tells it is wrong
+  extract <day> <page> [--block <n>] [--force]
+                             Write inputs/day<n>/test.txt and its expected answers from a puzzle
+                             page saved as HTML, taking the n-th <pre><code> block (default: 1)
+                             and the last emphasized code of each part
+  new <day>                  Create src/day<n>.rs from day.template with empty inputs, and register it
+  list                       List registered days
+  help                       Print this message
+
+Options:
+  --year <year>              Edition to run (default: the latest one)
+  --test                     Use inputs/day<n>/test.txt instead of inputs/day<n>/input.txt
+  --input <path>             Read the input from a file, or from stdin when the path is '-'
+  --record                   Store the answers for the real input in inputs/day<n>/answers.txt
+  --format <format>          Print results as text (default), json or csv, or for run only in the
+                             answers.txt format with answers
+  --jobs <n>                 Number of threads running solvers in parallel (default: 1)
+  --warmup <n>               Number of untimed bench runs before measuring (default: 10)
+  --iterations <n>           Number of timed bench runs (default: 100)
+  --compare                  Fail when a median is slower than its last recorded one
+  --threshold <percent>      Slowdown tolerated by --compare (default: 10)
+  --seed <n>                 Seed of the random input generator (default: 0)
+  --seeds <n>                Number of seeds checked by diff (default: 100)
+  --size <n>                 Scale of the generated input, as lines, monkeys or the side of a grid
+                             depending on the day (default: 100, or 10 for diff)
+  --block <n>                Code block of the page holding the example (default: 1)
+  --force                    Overwrite examples that already have content
+  --timings                  Add the time of each part to the report, a snapshot of this machine
+  -h, --help                 Print this message";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Csv,
+    /// The `answers.txt` format, only for a single day.
+    Answers,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    DataSet(DataSet),
+    File(PathBuf),
+    Stdin,
+}
+
+impl Input {
+    /// Short name of the input for machine-readable output: `real`, `test`, the path or `stdin`.
+    pub fn name(&self) -> String {
+        match self {
+            Self::DataSet(data_set) => data_set.to_string(),
+            Self::File(path) => path.display().to_string(),
+            Self::Stdin => "stdin".to_owned(),
+        }
+    }
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DataSet(data_set) => write!(f, "{data_set} data"),
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/// The parsed command line: the command and the edition it applies to.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Args {
+    pub year: u16,
+    pub command: Command,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run {
+        day: usize,
+        part: Option<usize>,
+        input: Input,
+        record: bool,
+        format: OutputFormat,
+    },
+    Test {
+        day: usize,
+    },
+    Watch {
+        day: usize,
+    },
+    RunAll {
+        data_set: DataSet,
+        record: bool,
+        format: OutputFormat,
+        jobs: usize,
+    },
+    Bench {
+        day: Option<usize>,
+        part: Option<usize>,
+        input: Option<Input>,
+        options: BenchOptions,
+        /// The slowdown in percent tolerated when comparing with the history instead of recording.
+        compare: Option<usize>,
+    },
+    Gen {
+        day: usize,
+        seed: u64,
+        size: usize,
+    },
+    Diff {
+        day: usize,
+        seeds: Range<u64>,
+        size: usize,
+    },
+    Report {
+        /// Whether the times of the run are shown, which change with every run.
+        timings: bool,
+    },
+    Fetch {
+        day: usize,
+    },
+    Submit {
+        day: usize,
+        part: usize,
+    },
+    Extract {
+        day: usize,
+        page: PathBuf,
+        block: usize,
+        force: bool,
+    },
+    New {
+        day: usize,
+    },
+    List,
+    Help,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseArgsError {
+    MissingCommand,
+    UnknownYear(String, Vec<u16>),
+    UnknownCommand(String),
+    MissingDay,
+    InvalidDay(String),
+    DayOutOfRange(usize, usize),
+    MissingPart,
+    MissingPage,
+    InvalidPart(String),
+    MissingFlagValue(String),
+    InvalidFlagValue(String, String),
+    ZeroFlagValue(String),
+    InvalidFormat(String),
+    ConflictingFlags(&'static str, &'static str),
+    UnexpectedArgument(String),
+}
+
+impl Display for ParseArgsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingCommand => write!(f, "missing command"),
+            Self::UnknownYear(year, years) => {
+                let years = years
+                    .iter()
+                    .map(u16::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "year should be one of {years}, got '{year}'")
+            }
+            Self::UnknownCommand(command) => write!(f, "unknown command '{command}'"),
+            Self::MissingDay => write!(f, "missing day"),
+            Self::InvalidDay(day) => write!(f, "day should be a number, got '{day}'"),
+            Self::DayOutOfRange(day, days) => {
+                write!(f, "day should be a number from 1 to {days}, got {day}")
+            }
+            Self::MissingPart => write!(f, "missing part"),
+            Self::MissingPage => write!(f, "missing path to the saved puzzle page"),
+            Self::InvalidPart(part) => {
+                write!(f, "part should be equal to 1 or 2, got '{part}'")
+            }
+            Self::MissingFlagValue(flag) => write!(f, "missing value for {flag}"),
+            Self::InvalidFlagValue(flag, value) => {
+                write!(f, "{flag} should be a number, got '{value}'")
+            }
+            Self::ZeroFlagValue(flag) => write!(f, "{flag} should be at least 1"),
+            Self::InvalidFormat(format) => {
+                write!(
+                    f,
+                    "format should be text, json, csv or answers, got '{format}'"
+                )
+            }
+            Self::ConflictingFlags(flag, other_flag) => {
+                write!(f, "{flag} cannot be used together with {other_flag}")
+            }
+            Self::UnexpectedArgument(arg) => write!(f, "unexpected argument '{arg}'"),
+        }
+    }
+}
+
+/// Slowdown in percent tolerated by `bench --compare` when `--threshold` is omitted.
+const DEFAULT_THRESHOLD: usize = 10;
+
+/// Scale of generated inputs when `--size` is omitted.
+const DEFAULT_SIZE: usize = 100;
+
+/// Number of seeds and scale of the inputs checked by `diff` when `--seeds` and `--size` are
+/// omitted, small enough for the reference solutions.
+const DEFAULT_DIFF_SEEDS: usize = 100;
+const DEFAULT_DIFF_SIZE: usize = 10;
+
+/// Flags that consume the argument following them as their value.
+const VALUED_FLAGS: [&str; 11] = [
+    "--block",
+    "--format",
+    "--input",
+    "--iterations",
+    "--jobs",
+    "--seed",
+    "--seeds",
+    "--size",
+    "--threshold",
+    "--warmup",
+    "--year",
+];
+
+/// Command-line flags collected before dispatching on the command, so that each command
+/// takes the flags it understands and anything left over can be reported.
+struct Flags {
+    flags: Vec<(String, Option<String>)>,
+}
+
+impl Flags {
+    fn take_switch(&mut self, name: &str) -> bool {
+        self.take(name).is_some()
+    }
+
+    fn take_value(&mut self, name: &str) -> Option<String> {
+        self.take(name).flatten()
+    }
+
+    fn take_count(&mut self, name: &str, default: usize) -> Result<usize, ParseArgsError> {
+        match self.take_value(name) {
+            Some(value) => value
+                .parse()
+                .map_err(|_| ParseArgsError::InvalidFlagValue(name.to_owned(), value)),
+            None => Ok(default),
+        }
+    }
+
+    /// Like [`Flags::take_count`], for counts that must be at least 1.
+    fn take_positive_count(&mut self, name: &str, default: usize) -> Result<usize, ParseArgsError> {
+        match self.take_count(name, default)? {
+            0 => Err(ParseArgsError::ZeroFlagValue(name.to_owned())),
+            count => Ok(count),
+        }
+    }
+
+    fn take(&mut self, name: &str) -> Option<Option<String>> {
+        let index = self.flags.iter().position(|(flag, _)| flag == name)?;
+
+        Some(self.flags.remove(index).1)
+    }
+
+    fn finish(self) -> Result<(), ParseArgsError> {
+        match self.flags.into_iter().next() {
+            Some((flag, _)) => Err(ParseArgsError::UnexpectedArgument(flag)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parses command-line arguments (without the program name) into [`Args`].
+///
+/// `years` lists the registered editions, oldest first, with their number of registered days which
+/// bounds the accepted day numbers. The latest edition is used when `--year` is omitted.
+pub fn parse_args<I: IntoIterator<Item = String>>(
+    args: I,
+    years: &[(u16, usize)],
+) -> Result<Args, ParseArgsError> {
+    let mut flags = Flags { flags: vec![] };
+    let mut positionals = vec![];
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "-h" || arg == "--help" {
+            return Ok(Args {
+                year: years.last().map_or(0, |&(year, _)| year),
+                command: Command::Help,
+            });
+        }
+
+        if VALUED_FLAGS.contains(&arg.as_str()) {
+            let value = args
+                .next()
+                .ok_or_else(|| ParseArgsError::MissingFlagValue(arg.clone()))?;
+            flags.flags.push((arg, Some(value)));
+        } else if arg.starts_with('-') {
+            flags.flags.push((arg, None));
+        } else {
+            positionals.push(arg);
+        }
+    }
+
+    let (year, days) = year(&mut flags, years)?;
+
+    let mut positionals = positionals.into_iter();
+    let command = positionals.next().ok_or(ParseArgsError::MissingCommand)?;
+
+    let parsed_command = match command.as_str() {
+        "run" => {
+            let input = input(&mut flags);
+            let (data_set, record) = data_set_and_record(&mut flags)?;
+
+            let input = match (input, data_set) {
+                (Some(input), DataSet::Real) if !record => input,
+                (Some(_), DataSet::Real) => {
+                    return Err(ParseArgsError::ConflictingFlags("--record", "--input"))
+                }
+                (Some(_), DataSet::Test) => {
+                    return Err(ParseArgsError::ConflictingFlags("--input", "--test"))
+                }
+                (None, data_set) => Input::DataSet(data_set),
+            };
+
+            Command::Run {
+                day: parse_day(positionals.next(), days)?,
+                part: positionals.next().map(parse_part).transpose()?,
+                input,
+                record,
+                format: format(&mut flags)?,
+            }
+        }
+        "all" => {
+            let (data_set, record) = data_set_and_record(&mut flags)?;
+            let jobs = flags.take_positive_count("--jobs", 1)?;
+            let format = format(&mut flags)?;
+
+            // answers files hold the answers of a single day
+            if format == OutputFormat::Answers {
+                return Err(ParseArgsError::ConflictingFlags("--format answers", "all"));
+            }
+
+            Command::RunAll {
+                data_set,
+                record,
+                format,
+                jobs,
+            }
+        }
+        "test" => Command::Test {
+            day: parse_day(positionals.next(), days)?,
+        },
+        "watch" => Command::Watch {
+            day: parse_day(positionals.next(), days)?,
+        },
+        "bench" => {
+            let day = positionals
+                .next()
+                .map(|day| parse_day(Some(day), days))
+                .transpose()?;
+            let part = positionals.next().map(parse_part).transpose()?;
+            let input = input(&mut flags);
+            let warmup = flags.take_count("--warmup", BenchOptions::DEFAULT_WARMUP)?;
+            let iterations =
+                flags.take_positive_count("--iterations", BenchOptions::DEFAULT_ITERATIONS)?;
+
+            // --threshold is left over, and reported, without --compare
+            let compare = if flags.take_switch("--compare") {
+                Some(flags.take_count("--threshold", DEFAULT_THRESHOLD)?)
+            } else {
+                None
+            };
+
+            // an input file only makes sense for the day it was written for
+            if input.is_some() && day.is_none() {
+                return Err(ParseArgsError::MissingDay);
+            }
+
+            // the history only holds benchmarks of the real inputs
+            if input.is_some() && compare.is_some() {
+                return Err(ParseArgsError::ConflictingFlags("--compare", "--input"));
+            }
+
+            Command::Bench {
+                day,
+                part,
+                input,
+                options: BenchOptions { warmup, iterations },
+                compare,
+            }
+        }
+        "gen" => {
+            let day = parse_day(positionals.next(), days)?;
+            let seed = flags.take_count("--seed", 0)?;
+            let size = flags.take_positive_count("--size", DEFAULT_SIZE)?;
+
+            Command::Gen {
+                day,
+                seed: seed as u64,
+                size,
+            }
+        }
+        "diff" => {
+            let day = parse_day(positionals.next(), days)?;
+            let seed = flags.take_count("--seed", 0)? as u64;
+            let seeds = flags.take_positive_count("--seeds", DEFAULT_DIFF_SEEDS)?;
+            let size = flags.take_positive_count("--size", DEFAULT_DIFF_SIZE)?;
+
+            Command::Diff {
+                day,
+                seeds: seed..seed.saturating_add(seeds as u64),
+                size,
+            }
+        }
+        "report" => Command::Report {
+            timings: flags.take_switch("--timings"),
+        },
+        "submit" => Command::Submit {
+            day: parse_day(positionals.next(), days)?,
+            part: parse_part(positionals.next().ok_or(ParseArgsError::MissingPart)?)?,
+        },
+        // days not registered yet can be fetched and created, so any day of the edition is accepted
+        "fetch" => Command::Fetch {
+            day: parse_day(positionals.next(), scaffold::LAST_DAY)?,
+        },
+        "extract" => {
+            let day = parse_day(positionals.next(), scaffold::LAST_DAY)?;
+            let page = positionals.next().ok_or(ParseArgsError::MissingPage)?;
+            let block = flags.take_positive_count("--block", 1)?;
+
+            Command::Extract {
+                day,
+                page: PathBuf::from(page),
+                block,
+                force: flags.take_switch("--force"),
+            }
+        }
+        "new" => Command::New {
+            day: parse_day(positionals.next(), scaffold::LAST_DAY)?,
+        },
+        "list" => Command::List,
+        "help" => Command::Help,
+        _ => return Err(ParseArgsError::UnknownCommand(command)),
+    };
+
+    flags.finish()?;
+
+    match positionals.next() {
+        Some(arg) => Err(ParseArgsError::UnexpectedArgument(arg)),
+        None => Ok(Args {
+            year,
+            command: parsed_command,
+        }),
+    }
+}
+
+fn year(flags: &mut Flags, years: &[(u16, usize)]) -> Result<(u16, usize), ParseArgsError> {
+    let unknown_year = |year: String| {
+        let registered_years = years.iter().map(|&(year, _)| year).collect();
+        ParseArgsError::UnknownYear(year, registered_years)
+    };
+
+    match flags.take_value("--year") {
+        Some(year) => {
+            let Ok(parsed_year) = year.parse::<u16>() else {
+                return Err(unknown_year(year));
+            };
+
+            years
+                .iter()
+                .find(|&&(registered_year, _)| registered_year == parsed_year)
+                .copied()
+                .ok_or_else(|| unknown_year(year))
+        }
+        None => years
+            .last()
+            .copied()
+            .ok_or_else(|| unknown_year(String::new())),
+    }
+}
+
+fn input(flags: &mut Flags) -> Option<Input> {
+    flags.take_value("--input").map(|path| match path.as_str() {
+        "-" => Input::Stdin,
+        _ => Input::File(PathBuf::from(path)),
+    })
+}
+
+fn format(flags: &mut Flags) -> Result<OutputFormat, ParseArgsError> {
+    match flags.take_value("--format").as_deref() {
+        None | Some("text") => Ok(OutputFormat::Text),
+        Some("json") => Ok(OutputFormat::Json),
+        Some("csv") => Ok(OutputFormat::Csv),
+        Some("answers") => Ok(OutputFormat::Answers),
+        Some(format) => Err(ParseArgsError::InvalidFormat(format.to_owned())),
+    }
+}
+
+fn data_set_and_record(flags: &mut Flags) -> Result<(DataSet, bool), ParseArgsError> {
+    let use_test_data = flags.take_switch("--test");
+    let record = flags.take_switch("--record");
+
+    // only answers for the real input are recorded
+    if use_test_data && record {
+        return Err(ParseArgsError::ConflictingFlags("--record", "--test"));
+    }
+
+    let data_set = if use_test_data {
+        DataSet::Test
+    } else {
+        DataSet::Real
+    };
+
+    Ok((data_set, record))
+}
+
+fn parse_day(day: Option<String>, days: usize) -> Result<usize, ParseArgsError> {
+    let day = day.ok_or(ParseArgsError::MissingDay)?;
+    let day = day
+        .parse::<usize>()
+        .map_err(|_| ParseArgsError::InvalidDay(day))?;
+
+    if day == 0 || day > days {
+        return Err(ParseArgsError::DayOutOfRange(day, days));
+    }
+
+    Ok(day)
+}
+
+fn parse_part(part: String) -> Result<usize, ParseArgsError> {
+    match part.as_str() {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(ParseArgsError::InvalidPart(part)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{
+        parse_args, Args, BenchOptions, Command, DataSet, Input, OutputFormat, ParseArgsError,
+    };
+
+    const YEARS: [(u16, usize); 2] = [(2021, 25), (2022, 13)];
+
+    fn parse_with_year(args: &[&str]) -> Result<Args, ParseArgsError> {
+        parse_args(args.iter().map(|arg| arg.to_string()), &YEARS)
+    }
+
+    fn parse(args: &[&str]) -> Result<Command, ParseArgsError> {
+        parse_with_year(args).map(|args| args.command)
+    }
+
+    #[test]
+    fn year_defaults_to_the_latest_edition() {
+        assert_eq!(
+            parse_with_year(&["test", "3"]),
+            Ok(Args {
+                year: 2022,
+                command: Command::Test { day: 3 }
+            })
+        );
+        assert_eq!(
+            parse_with_year(&["--year", "2021", "test", "20"]),
+            Ok(Args {
+                year: 2021,
+                command: Command::Test { day: 20 }
+            })
+        );
+    }
+
+    #[test]
+    fn year_bounds_the_days_and_should_be_registered() {
+        assert_eq!(
+            parse(&["test", "20"]),
+            Err(ParseArgsError::DayOutOfRange(20, 13))
+        );
+        assert_eq!(
+            parse(&["list", "--year", "2015"]),
+            Err(ParseArgsError::UnknownYear(
+                "2015".to_owned(),
+                vec![2021, 2022]
+            ))
+        );
+    }
+
+    #[test]
+    fn run_accepts_day_part_and_test_flag() {
+        assert_eq!(
+            parse(&["run", "12", "2", "--test"]),
+            Ok(Command::Run {
+                day: 12,
+                part: Some(2),
+                input: Input::DataSet(DataSet::Test),
+                record: false,
+                format: OutputFormat::Text
+            })
+        );
+    }
+
+    #[test]
+    fn test_checks_examples_of_a_day() {
+        assert_eq!(parse(&["test", "3"]), Ok(Command::Test { day: 3 }));
+    }
+
+    #[test]
+    fn watch_needs_a_registered_day() {
+        assert_eq!(parse(&["watch", "3"]), Ok(Command::Watch { day: 3 }));
+        assert_eq!(
+            parse(&["watch", "14"]),
+            Err(ParseArgsError::DayOutOfRange(14, 13))
+        );
+    }
+
+    #[test]
+    fn submit_needs_a_day_and_a_part() {
+        assert_eq!(
+            parse(&["submit", "7", "2"]),
+            Ok(Command::Submit { day: 7, part: 2 })
+        );
+        assert_eq!(parse(&["submit", "7"]), Err(ParseArgsError::MissingPart));
+    }
+
+    #[test]
+    fn new_accepts_any_day_of_the_edition() {
+        assert_eq!(parse(&["new", "14"]), Ok(Command::New { day: 14 }));
+        assert_eq!(parse(&["fetch", "25"]), Ok(Command::Fetch { day: 25 }));
+        assert_eq!(
+            parse(&["new", "26"]),
+            Err(ParseArgsError::DayOutOfRange(26, 25))
+        );
+    }
+
+    #[test]
+    fn extract_needs_a_day_and_a_page() {
+        assert_eq!(
+            parse(&["extract", "18", "day18.html", "--block", "2"]),
+            Ok(Command::Extract {
+                day: 18,
+                page: PathBuf::from("day18.html"),
+                block: 2,
+                force: false
+            })
+        );
+        assert_eq!(
+            parse(&["extract", "1", "--force", "day1.html"]),
+            Ok(Command::Extract {
+                day: 1,
+                page: PathBuf::from("day1.html"),
+                block: 1,
+                force: true
+            })
+        );
+        assert_eq!(parse(&["extract", "1"]), Err(ParseArgsError::MissingPage));
+    }
+
+    #[test]
+    fn run_reads_input_from_file_or_stdin() {
+        assert_eq!(
+            parse(&["run", "9", "--input", "stress.txt"]),
+            Ok(Command::Run {
+                day: 9,
+                part: None,
+                input: Input::File(PathBuf::from("stress.txt")),
+                record: false,
+                format: OutputFormat::Text
+            })
+        );
+        assert_eq!(
+            parse(&["run", "9", "2", "--input", "-"]),
+            Ok(Command::Run {
+                day: 9,
+                part: Some(2),
+                input: Input::Stdin,
+                record: false,
+                format: OutputFormat::Text
+            })
+        );
+        assert_eq!(
+            parse(&["run", "9", "--input", "-", "--test"]),
+            Err(ParseArgsError::ConflictingFlags("--input", "--test"))
+        );
+        assert_eq!(
+            parse(&["run", "9", "--input", "-", "--record"]),
+            Err(ParseArgsError::ConflictingFlags("--record", "--input"))
+        );
+        assert_eq!(
+            parse(&["bench", "--input", "-"]),
+            Err(ParseArgsError::MissingDay)
+        );
+    }
+
+    #[test]
+    fn all_accepts_test_flag_and_jobs() {
+        assert_eq!(
+            parse(&["all", "--test"]),
+            Ok(Command::RunAll {
+                data_set: DataSet::Test,
+                record: false,
+                format: OutputFormat::Text,
+                jobs: 1
+            })
+        );
+        assert_eq!(
+            parse(&["all", "--jobs", "4"]),
+            Ok(Command::RunAll {
+                data_set: DataSet::Real,
+                record: false,
+                format: OutputFormat::Text,
+                jobs: 4
+            })
+        );
+        assert_eq!(
+            parse(&["all", "--jobs", "0"]),
+            Err(ParseArgsError::ZeroFlagValue("--jobs".to_owned()))
+        );
+    }
+
+    #[test]
+    fn gen_takes_seed_and_size() {
+        assert_eq!(
+            parse(&["gen", "7", "--seed", "42", "--size", "5000"]),
+            Ok(Command::Gen {
+                day: 7,
+                seed: 42,
+                size: 5000
+            })
+        );
+        assert_eq!(
+            parse(&["gen", "13"]),
+            Ok(Command::Gen {
+                day: 13,
+                seed: 0,
+                size: 100
+            })
+        );
+        assert_eq!(
+            parse(&["gen", "8", "--size", "0"]),
+            Err(ParseArgsError::ZeroFlagValue("--size".to_owned()))
+        );
+    }
+
+    #[test]
+    fn diff_takes_seed_range_and_size() {
+        assert_eq!(
+            parse(&["diff", "11", "--seed", "5", "--seeds", "20", "--size", "3"]),
+            Ok(Command::Diff {
+                day: 11,
+                seeds: 5..25,
+                size: 3
+            })
+        );
+        assert_eq!(
+            parse(&["diff", "4"]),
+            Ok(Command::Diff {
+                day: 4,
+                seeds: 0..100,
+                size: 10
+            })
+        );
+        assert_eq!(
+            parse(&["diff", "12", "--seeds", "0"]),
+            Err(ParseArgsError::ZeroFlagValue("--seeds".to_owned()))
+        );
+    }
+
+    #[test]
+    fn report_takes_only_the_timings_switch() {
+        assert_eq!(parse(&["report"]), Ok(Command::Report { timings: false }));
+        assert_eq!(
+            parse(&["report", "--timings"]),
+            Ok(Command::Report { timings: true })
+        );
+        assert_eq!(
+            parse(&["report", "3"]),
+            Err(ParseArgsError::UnexpectedArgument("3".to_owned()))
+        );
+    }
+
+    #[test]
+    fn run_and_all_accept_output_format() {
+        assert_eq!(
+            parse(&["run", "10", "--format", "json"]),
+            Ok(Command::Run {
+                day: 10,
+                part: None,
+                input: Input::DataSet(DataSet::Real),
+                record: false,
+                format: OutputFormat::Json
+            })
+        );
+        assert_eq!(
+            parse(&["all", "--format", "csv", "--test"]),
+            Ok(Command::RunAll {
+                data_set: DataSet::Test,
+                record: false,
+                format: OutputFormat::Csv,
+                jobs: 1
+            })
+        );
+        assert_eq!(
+            parse(&["all", "--format", "yaml"]),
+            Err(ParseArgsError::InvalidFormat("yaml".to_owned()))
+        );
+        assert_eq!(
+            parse(&["run", "10", "--test", "--format", "answers"]),
+            Ok(Command::Run {
+                day: 10,
+                part: None,
+                input: Input::DataSet(DataSet::Test),
+                record: false,
+                format: OutputFormat::Answers
+            })
+        );
+        assert_eq!(
+            parse(&["all", "--format", "answers"]),
+            Err(ParseArgsError::ConflictingFlags("--format answers", "all"))
+        );
+        assert_eq!(
+            parse(&["test", "1", "--format", "json"]),
+            Err(ParseArgsError::UnexpectedArgument("--format".to_owned()))
+        );
+    }
+
+    #[test]
+    fn bench_accepts_optional_day_and_run_counts() {
+        assert_eq!(
+            parse(&["bench", "--iterations", "20", "11", "--warmup", "2"]),
+            Ok(Command::Bench {
+                day: Some(11),
+                part: None,
+                input: None,
+                options: BenchOptions {
+                    warmup: 2,
+                    iterations: 20
+                },
+                compare: None
+            })
+        );
+        assert_eq!(
+            parse(&["bench"]),
+            Ok(Command::Bench {
+                day: None,
+                part: None,
+                input: None,
+                options: BenchOptions::default(),
+                compare: None
+            })
+        );
+        assert_eq!(
+            parse(&["bench", "6", "--compare", "--threshold", "25"]),
+            Ok(Command::Bench {
+                day: Some(6),
+                part: None,
+                input: None,
+                options: BenchOptions::default(),
+                compare: Some(25)
+            })
+        );
+        assert_eq!(
+            parse(&["bench", "--compare"]),
+            Ok(Command::Bench {
+                day: None,
+                part: None,
+                input: None,
+                options: BenchOptions::default(),
+                compare: Some(10)
+            })
+        );
+        assert_eq!(
+            parse(&["bench", "--threshold", "25"]),
+            Err(ParseArgsError::UnexpectedArgument("--threshold".to_owned()))
+        );
+        assert_eq!(
+            parse(&["bench", "--iterations"]),
+            Err(ParseArgsError::MissingFlagValue("--iterations".to_owned()))
+        );
+        assert_eq!(
+            parse(&["bench", "--warmup", "many"]),
+            Err(ParseArgsError::InvalidFlagValue(
+                "--warmup".to_owned(),
+                "many".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn flag_errors_name_the_constraint_broken() {
+        assert_eq!(
+            parse(&["gen", "1", "--seed", "abc"])
+                .unwrap_err()
+                .to_string(),
+            "--seed should be a number, got 'abc'"
+        );
+        assert_eq!(
+            parse(&["gen", "1", "--seed", "0"]),
+            Ok(Command::Gen {
+                day: 1,
+                seed: 0,
+                size: 100
+            })
+        );
+        assert_eq!(
+            parse(&["all", "--jobs", "0"]).unwrap_err().to_string(),
+            "--jobs should be at least 1"
+        );
+    }
+
+    #[test]
+    fn day_is_validated_against_registered_solvers() {
+        assert_eq!(
+            parse(&["run", "14"]),
+            Err(ParseArgsError::DayOutOfRange(14, 13))
+        );
+        assert_eq!(
+            parse(&["run", "0"]),
+            Err(ParseArgsError::DayOutOfRange(0, 13))
+        );
+        assert_eq!(
+            parse(&["run", "x"]),
+            Err(ParseArgsError::InvalidDay("x".to_owned()))
+        );
+    }
+
+    #[test]
+    fn missing_and_unexpected_arguments_are_reported() {
+        assert_eq!(parse(&[]), Err(ParseArgsError::MissingCommand));
+        assert_eq!(parse(&["run"]), Err(ParseArgsError::MissingDay));
+        assert_eq!(
+            parse(&["run", "1", "3"]),
+            Err(ParseArgsError::InvalidPart("3".to_owned()))
+        );
+        assert_eq!(
+            parse(&["list", "1"]),
+            Err(ParseArgsError::UnexpectedArgument("1".to_owned()))
+        );
+        assert_eq!(
+            parse(&["list", "--test"]),
+            Err(ParseArgsError::UnexpectedArgument("--test".to_owned()))
+        );
+    }
+
+    #[test]
+    fn help_flag_wins_over_other_arguments() {
+        assert_eq!(parse(&["run", "--help"]), Ok(Command::Help));
+        assert_eq!(parse(&["-h"]), Ok(Command::Help));
+    }
+}
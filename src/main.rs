@@ -0,0 +1,627 @@
+use std::{
+    collections::HashMap,
+    env::{self, args},
+    fmt::Display,
+    fs,
+    path::Path,
+    process::{self, Stdio},
+    str::FromStr,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aoc_framework::{
+    answers::{Answers, Verdict},
+    bench,
+    bench_history::{self, BenchHistory},
+    client::{self, Client, FetchOutcome},
+    differential,
+    extract::{self, PuzzlePage},
+    generate, output, report, runner, scaffold,
+    shared::{self, DataSet, Edition},
+    submit::{self, History, SubmitError, SubmitOutcome},
+    watch::{self, Snapshot},
+};
+use cli::{Args, Command, Input, OutputFormat};
+
+mod cli;
+
+/// Counts allocations for the summaries of `all` and `bench`, at the cost of some overhead.
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: aoc_framework::memory::CountingAllocator =
+    aoc_framework::memory::CountingAllocator;
+
+/// Registered editions, oldest first. The last one is run when `--year` is omitted.
+const EDITIONS: &[Edition] = &[advent_of_code_2022::EDITION];
+
+fn main() {
+    let years = EDITIONS
+        .iter()
+        .map(|edition| (edition.year, edition.solvers.len()))
+        .collect::<Vec<_>>();
+
+    let Args { year, command } = match cli::parse_args(args().skip(1), &years) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("error: {err}\n\n{}", cli::USAGE);
+            process::exit(2);
+        }
+    };
+    let edition = EDITIONS
+        .iter()
+        .find(|edition| edition.year == year)
+        .expect("parsed year should be registered");
+
+    match command {
+        Command::Run {
+            day,
+            part,
+            input: input_source,
+            record,
+            format,
+        } => {
+            let input = read_input_or_exit(edition, day, &input_source);
+            let day_dir = edition.day_dir(day);
+            let mut answers = match &input_source {
+                Input::DataSet(data_set) => {
+                    Some(load_answers_or_exit(&day_dir, data_set.filename()))
+                }
+                _ => None,
+            };
+
+            let mut runs = runner::silence_panics(|| {
+                runner::run_parts(day, &edition.solvers[day - 1], &input, &parts(part))
+            });
+
+            if let Some(answers) = answers.as_mut() {
+                for run in &mut runs {
+                    run.verdict = Some(runner::check_answer(
+                        answers,
+                        run.part,
+                        &run.outcome,
+                        record,
+                    ));
+                }
+            }
+
+            match format {
+                OutputFormat::Text => {
+                    for run in &runs {
+                        match &run.outcome {
+                            Ok(result) => {
+                                print_result(
+                                    day,
+                                    run.part,
+                                    &input_source,
+                                    result,
+                                    run.verdict.as_ref(),
+                                );
+                            }
+                            Err(message) => {
+                                eprintln!("error: day {day} part {}: {message}", run.part)
+                            }
+                        }
+                    }
+                }
+                OutputFormat::Json => {
+                    println!("{}", output::render_json(&runs, &input_source.name()))
+                }
+                OutputFormat::Csv => print!("{}", output::render_csv(&runs, &input_source.name())),
+                OutputFormat::Answers => {
+                    for run in &runs {
+                        if let Err(message) = &run.outcome {
+                            eprintln!("error: day {day} part {}: {message}", run.part)
+                        }
+                    }
+                    print!("{}", output::render_answers(&runs));
+                }
+            }
+
+            if let (true, Some(answers)) = (record, &answers) {
+                answers
+                    .save(&day_dir, DataSet::Real.filename())
+                    .unwrap_or_else(|err| {
+                        eprintln!("error: could not record answers for day {day}: {err}");
+                        process::exit(1);
+                    });
+            }
+
+            if runs.iter().any(|run| run.is_failed()) {
+                process::exit(1);
+            }
+        }
+        Command::Test { day } => {
+            let day_dir = edition.day_dir(day);
+            let examples = shared::example_names(&day_dir).unwrap_or_else(|err| {
+                eprintln!("error: could not list examples for day {day}: {err}");
+                process::exit(1);
+            });
+
+            if examples.is_empty() {
+                eprintln!("error: day {day} has no test*.txt examples");
+                process::exit(1);
+            }
+
+            let solver = &edition.solvers[day - 1];
+            let mut failed = false;
+
+            for example in examples {
+                let input_source = Input::File(shared::input_path(&day_dir, &example));
+                let input = read_input_or_exit(edition, day, &input_source);
+                let answers = load_answers_or_exit(&day_dir, &example);
+                let runs =
+                    runner::silence_panics(|| runner::run_parts(day, solver, &input, &[1, 2]));
+
+                for run in runs {
+                    match &run.outcome {
+                        Ok(result) => {
+                            let verdict = answers.check(run.part, result);
+                            failed |= print_result(day, run.part, &example, result, Some(&verdict));
+                        }
+                        Err(message) => {
+                            eprintln!("error: day {day} part {} ({example}): {message}", run.part);
+                            failed = true;
+                        }
+                    }
+                }
+            }
+
+            if failed {
+                process::exit(1);
+            }
+        }
+        Command::Watch { day } => watch(edition, day),
+        Command::RunAll {
+            data_set,
+            record,
+            format,
+            jobs,
+        } => {
+            let all_runs = runner::run_all(edition, data_set, record, jobs);
+            let runs = &all_runs.runs;
+
+            match format {
+                OutputFormat::Text => runner::print_summary(&all_runs),
+                OutputFormat::Json => {
+                    println!("{}", output::render_json(runs, &data_set.to_string()))
+                }
+                OutputFormat::Csv => print!("{}", output::render_csv(runs, &data_set.to_string())),
+                OutputFormat::Answers => unreachable!("answers format should be rejected for all"),
+            }
+
+            if runs.iter().any(|run| run.is_failed()) {
+                process::exit(1);
+            }
+        }
+        Command::Bench {
+            day,
+            part,
+            input: input_source,
+            options,
+            compare,
+        } => {
+            // only benchmarks of the real inputs go to the history
+            let records_history = input_source.is_none();
+            let input_source = input_source.unwrap_or(Input::DataSet(DataSet::Real));
+            let days = day
+                .map(|day| vec![day])
+                .unwrap_or_else(|| (1..=edition.solvers.len()).collect());
+            let mut results = vec![];
+
+            for day in days {
+                let input = read_input_or_exit(edition, day, &input_source);
+
+                let day_results = bench::bench_solver(
+                    day,
+                    &edition.solvers[day - 1],
+                    &parts(part),
+                    &input,
+                    &options,
+                )
+                .unwrap_or_else(|err| {
+                    eprintln!("error: day {day}: {err}");
+                    process::exit(1);
+                });
+
+                results.extend(day_results);
+            }
+
+            bench::print_report(&results, &options);
+
+            if !records_history {
+                return;
+            }
+
+            let root = Path::new(edition.root);
+            let mut history = BenchHistory::load(root).unwrap_or_else(|err| {
+                let path = BenchHistory::path(root);
+                eprintln!("error: could not read {}: {err}", path.display());
+                process::exit(1);
+            });
+
+            if let Some(threshold) = compare {
+                let comparisons = bench_history::compare(&history, &results);
+
+                println!();
+                if comparisons.is_empty() {
+                    println!("no recorded baseline to compare with, run bench without --compare");
+                    return;
+                }
+                bench_history::print_comparison(&comparisons, threshold);
+
+                let regressions = comparisons
+                    .iter()
+                    .filter(|comparison| comparison.is_regression(threshold))
+                    .count();
+                if regressions > 0 {
+                    eprintln!("error: {regressions} step(s) slower by more than {threshold}%");
+                    process::exit(1);
+                }
+                return;
+            }
+
+            let commit = bench_history::current_commit(root);
+            history.record(&results, commit.as_deref(), unix_time());
+            history.save(root).unwrap_or_else(|err| {
+                let path = BenchHistory::path(root);
+                eprintln!("error: could not record in {}: {err}", path.display());
+                process::exit(1);
+            });
+        }
+        Command::Gen { day, seed, size } => {
+            match generate::generate(&edition.solvers[day - 1], seed, size) {
+                Some(input) => print!("{input}"),
+                None => {
+                    eprintln!("error: day {day} has no input generator");
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Diff { day, seeds, size } => {
+            let solver = &edition.solvers[day - 1];
+            if differential::references(solver).next().is_none() {
+                eprintln!("error: day {day} has no reference solution");
+                process::exit(1);
+            }
+            if generate::generate(solver, seeds.start, size).is_none() {
+                eprintln!("error: day {day} has no input generator");
+                process::exit(1);
+            }
+
+            let count = seeds.end - seeds.start;
+            let mismatch = runner::silence_panics(|| {
+                differential::find_mismatch(solver, seeds, size)
+                    .map(|mismatch| differential::shrink(solver, mismatch))
+            });
+
+            match mismatch {
+                None => println!("day {day} agrees with its reference solutions on {count} inputs"),
+                Some(mismatch) => {
+                    println!("{mismatch}");
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Report { timings } => {
+            // a single job, so that solvers do not slow each other down
+            let all_runs = runner::run_all(edition, DataSet::Real, false, 1);
+            let failed = all_runs.runs.iter().any(|run| run.is_failed());
+
+            let reports = report::day_reports(edition, all_runs).unwrap_or_else(|err| {
+                eprintln!("error: could not gather the report: {err}");
+                process::exit(1);
+            });
+            let table = report::render_table(year, &reports, timings);
+            let path = report::write_readme(edition, &table).unwrap_or_else(|err| {
+                eprintln!("error: could not write the report: {err}");
+                process::exit(1);
+            });
+
+            println!(
+                "wrote the report of {} days to {}",
+                reports.len(),
+                path.display()
+            );
+
+            if failed {
+                eprintln!("some solvers failed or gave a wrong answer, see the report");
+                process::exit(1);
+            }
+        }
+        Command::Fetch { day } => {
+            let outcome = Client::from_env()
+                .and_then(|client| {
+                    client::fetch_input(&client, edition.year, day, &edition.day_dir(day))
+                })
+                .unwrap_or_else(|err| {
+                    eprintln!("error: could not fetch the input of day {day}: {err}");
+                    process::exit(1);
+                });
+
+            match outcome {
+                FetchOutcome::Downloaded(path) => println!("downloaded {}", path.display()),
+                FetchOutcome::Cached(path) => {
+                    println!(
+                        "{} is already there, not downloading it again",
+                        path.display()
+                    )
+                }
+            }
+        }
+        Command::Submit { day, part } => {
+            let day_dir = edition.day_dir(day);
+            let input = read_input_or_exit(edition, day, &Input::DataSet(DataSet::Real));
+
+            let runs = runner::silence_panics(|| {
+                runner::run_parts(day, &edition.solvers[day - 1], &input, &[part])
+            });
+            let result = match &runs[0].outcome {
+                Ok(result) => result,
+                Err(message) => {
+                    eprintln!("error: day {day} part {part}: {message}");
+                    process::exit(1);
+                }
+            };
+            let answer = result.to_string();
+
+            let mut history = History::load(&day_dir).unwrap_or_else(|err| {
+                let path = History::path(&day_dir);
+                eprintln!("error: could not read {}: {err}", path.display());
+                process::exit(1);
+            });
+            let now = unix_time();
+
+            let outcome = Client::from_env()
+                .map_err(SubmitError::from)
+                .and_then(|client| {
+                    submit::submit_answer(
+                        &client,
+                        edition.year,
+                        day,
+                        part,
+                        &answer,
+                        &mut history,
+                        now,
+                    )
+                });
+
+            // attempts are recorded even when the command fails afterwards
+            history.save(&day_dir).unwrap_or_else(|err| {
+                let path = History::path(&day_dir);
+                eprintln!(
+                    "error: could not record the attempt in {}: {err}",
+                    path.display()
+                );
+            });
+
+            let outcome = outcome.unwrap_or_else(|err| {
+                eprintln!("error: day {day} part {part}: {err}");
+                process::exit(1);
+            });
+
+            println!("Submitted {answer} for day {day} part {part}: {outcome}");
+
+            if outcome != SubmitOutcome::Correct {
+                process::exit(1);
+            }
+
+            let mut answers = load_answers_or_exit(&day_dir, DataSet::Real.filename());
+            answers.record(part, result);
+            answers
+                .save(&day_dir, DataSet::Real.filename())
+                .unwrap_or_else(|err| {
+                    eprintln!("error: could not record answers for day {day}: {err}");
+                    process::exit(1);
+                });
+        }
+        Command::Extract {
+            day,
+            page,
+            block,
+            force,
+        } => {
+            let html = fs::read_to_string(&page).unwrap_or_else(|err| {
+                eprintln!("error: could not read {}: {err}", page.display());
+                process::exit(1);
+            });
+            let page = PuzzlePage::parse(&html);
+
+            let written = extract::write_example(&page, block, &edition.day_dir(day), force)
+                .unwrap_or_else(|err| {
+                    eprintln!("error: could not extract the example of day {day}: {err}");
+                    process::exit(1);
+                });
+
+            for path in written {
+                println!("wrote {}", path.display());
+            }
+            println!(
+                "used code block {block} of {}, check it is the example",
+                page.code_blocks.len()
+            );
+            for (part, answer) in (1..).zip(&page.answers) {
+                match answer {
+                    Some(answer) => println!("part {part}: {answer}"),
+                    None => println!("part {part}: no emphasized answer found"),
+                }
+            }
+        }
+        Command::New { day } => {
+            let root = Path::new(edition.root);
+
+            let created = scaffold::new_day(root, day).unwrap_or_else(|err| {
+                eprintln!("error: could not create day {day}: {err}");
+                process::exit(1);
+            });
+
+            for path in created {
+                println!("created {}", path.display());
+            }
+            println!("registered day {day} of {year} in src/registry.rs");
+        }
+        Command::List => {
+            for day in 1..=edition.solvers.len() {
+                println!("day {day}");
+            }
+        }
+        Command::Help => println!("{}", cli::USAGE),
+    }
+}
+
+/// Polls the files of the day, rebuilding the binary and running the day on both inputs after
+/// each change. The runs happen in a child process, as only a fresh binary has the new code.
+fn watch(edition: &Edition, day: usize) -> ! {
+    const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+    // resolved before the first build replaces the file the running binary was started from
+    let binary = env::current_exe().unwrap_or_else(|err| {
+        eprintln!("error: could not locate the running binary: {err}");
+        process::exit(1);
+    });
+    let root = Path::new(edition.root);
+    let day_dir = edition.day_dir(day);
+    let mut snapshot = Snapshot::default();
+    let mut previous_answers = HashMap::<String, Answers>::new();
+
+    println!(
+        "watching day {day} of {}, press Ctrl-C to stop",
+        edition.year
+    );
+
+    loop {
+        let current_snapshot = Snapshot::take(root, day).unwrap_or_else(|err| {
+            eprintln!("error: could not watch day {day}: {err}");
+            process::exit(1);
+        });
+
+        if current_snapshot == snapshot {
+            thread::sleep(POLL_INTERVAL);
+            continue;
+        }
+        snapshot = current_snapshot;
+
+        println!("\n--- day {day}: building");
+        if !build(root) {
+            println!("build failed, waiting for changes");
+            continue;
+        }
+
+        // every example checked by the test command, then the real input
+        let mut examples = shared::example_names(&day_dir).unwrap_or_default();
+        if examples.is_empty() {
+            examples.push(DataSet::Test.filename().to_owned());
+        }
+        let inputs = examples
+            .into_iter()
+            .map(|example| (example.clone(), example))
+            .chain([(
+                DataSet::Real.to_string(),
+                DataSet::Real.filename().to_owned(),
+            )]);
+
+        for (label, input_name) in inputs {
+            let input_path = shared::input_path(&day_dir, &input_name);
+            if !fs::metadata(&input_path).is_ok_and(|metadata| metadata.len() > 0) {
+                println!("{label}: no input at {}", input_path.display());
+                continue;
+            }
+
+            let mut command = process::Command::new(&binary);
+            command.args(["--year", &edition.year.to_string(), "run", &day.to_string()]);
+            command.args(["--format", "answers", "--input"]);
+            command.arg(&input_path);
+
+            // errors of the run are printed as they come, the answers are compared below
+            let output = command.stderr(Stdio::inherit()).output();
+            let answers = output.map_err(|err| err.to_string()).and_then(|output| {
+                Answers::from_str(&String::from_utf8_lossy(&output.stdout))
+                    .map_err(|err| err.to_string())
+            });
+            let answers = match answers {
+                Ok(answers) => answers,
+                Err(err) => {
+                    println!("{label}: could not run day {day}: {err}");
+                    continue;
+                }
+            };
+
+            let expected = load_answers_or_exit(&day_dir, &input_name);
+            for line in watch::diff(previous_answers.get(&input_name), &answers, &expected) {
+                println!("{label} {line}");
+            }
+
+            previous_answers.insert(input_name, answers);
+        }
+    }
+}
+
+/// Builds the binary with the profile it was built with, from the year's crate so that cargo finds
+/// the workspace. Returns whether the build succeeded; cargo reports the errors itself.
+fn build(root: &Path) -> bool {
+    let mut command = process::Command::new(env::var_os("CARGO").unwrap_or("cargo".into()));
+    command.args(["build", "--quiet", "--package", env!("CARGO_PKG_NAME")]);
+    command.args(["--bin", env!("CARGO_BIN_NAME")]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+
+    command
+        .current_dir(root)
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock should be after the Unix epoch")
+        .as_secs()
+}
+
+fn parts(part: Option<usize>) -> Vec<usize> {
+    part.map(|part| vec![part]).unwrap_or_else(|| vec![1, 2])
+}
+
+/// Prints the result with its verdict, returning whether it was a wrong answer.
+fn print_result(
+    day: usize,
+    part: usize,
+    input: &dyn Display,
+    result: &shared::PuzzleResult,
+    verdict: Option<&Verdict>,
+) -> bool {
+    let (verdict, wrong_answer) = match verdict {
+        Some(Verdict::Wrong(expected)) => (format!(" ✗ (expected: {expected})"), true),
+        Some(verdict) => (format!(" {verdict}"), false),
+        None => (String::new(), false),
+    };
+
+    println!("Result for day {day} part {part} ({input}): {result}{verdict}");
+
+    wrong_answer
+}
+
+fn load_answers_or_exit(day_dir: &Path, input_name: &str) -> Answers {
+    Answers::load(day_dir, input_name).unwrap_or_else(|err| {
+        let path = Answers::path(day_dir, input_name);
+        eprintln!("error: could not read {}: {err}", path.display());
+        process::exit(1);
+    })
+}
+
+fn read_input_or_exit(edition: &Edition, day: usize, input: &Input) -> String {
+    let (result, path) = match input {
+        Input::DataSet(data_set) => {
+            let path = shared::input_path(&edition.day_dir(day), data_set.filename());
+            (fs::read_to_string(&path), path.display().to_string())
+        }
+        Input::File(path) => (fs::read_to_string(path), path.display().to_string()),
+        Input::Stdin => (shared::read_stdin(), "stdin".to_owned()),
+    };
+
+    result.unwrap_or_else(|err| {
+        eprintln!("error: could not read {path}: {err}");
+        process::exit(1);
+    })
+}